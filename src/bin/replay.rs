@@ -0,0 +1,153 @@
+use mars_bot::{replay, snapshot};
+
+fn main()
+{
+    match try_main() {
+        Ok(true) => {}
+        Ok(false) => ::std::process::exit(1),
+        Err(err) => panic!("{err:?}"),
+    }
+}
+
+fn try_main() -> ::std::result::Result<bool, Error>
+{
+    let mut args = ::std::env::args().skip(1);
+    let replay_path = args.next().ok_or(Error::MissingPath)?;
+
+    let replay = replay::try_parse(::std::fs::read_to_string(replay_path)?)?;
+
+    // NOTE: Lets bug reports and tests start from
+    // the bot's state mid-game instead of replaying up to it
+    match (args.next().as_deref(), args.next()) {
+        (None, _) => {}
+        (Some("--snapshot-at"), Some(round)) => {
+            let round = round.parse().map_err(|_| Error::InvalidRound { round })?;
+            let bot =
+                replay::bot_after(&replay, round)?.ok_or(Error::RoundNotRecorded { round })?;
+
+            print!("{}", snapshot::show(&bot));
+
+            return Ok(true);
+        }
+        (Some(flag), _) => return Err(Error::UnknownFlag { flag: flag.into() }),
+    }
+
+    let replay::Report {
+        turns,
+        divergences,
+        recorded_elapsed,
+        replayed_elapsed,
+    } = replay::run(&replay)?;
+
+    println!(
+        "replayed {turns} turns recorded by bot {}",
+        replay.header.bot_version
+    );
+    println!("time spent in turns: recorded {recorded_elapsed:?}, replayed {replayed_elapsed:?}");
+
+    for replay::Divergence {
+        round,
+        recorded,
+        replayed,
+    } in &divergences
+    {
+        println!("\nround {round} diverged");
+        println!("recorded:\n{recorded}");
+        println!("replayed:\n{replayed}");
+    }
+
+    if divergences.is_empty() {
+        println!("no divergences");
+    } else {
+        println!("\n{} of {turns} turns diverged", divergences.len());
+    }
+
+    Ok(divergences.is_empty())
+}
+
+#[derive(Debug)]
+enum Error
+{
+    MissingPath,
+    UnknownFlag
+    {
+        flag: String,
+    },
+    InvalidRound
+    {
+        round: String,
+    },
+    RoundNotRecorded
+    {
+        round: usize,
+    },
+    Io
+    {
+        io_err: ::std::io::Error,
+    },
+
+    Replay
+    {
+        replay_err: replay::Error,
+    },
+    Bot
+    {
+        bot_err: mars_bot::Error,
+    },
+}
+
+impl ::std::fmt::Display for Error
+{
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result
+    {
+        match self {
+            Error::MissingPath => write!(f, "missing replay path"),
+            Error::UnknownFlag { flag } => write!(f, "unknown argument {flag}"),
+            Error::InvalidRound { round } => write!(f, "invalid round {round}"),
+            Error::RoundNotRecorded { round } => write!(f, "round {round} isn't in the replay"),
+            Error::Io { io_err } => write!(f, "{io_err}"),
+            Error::Replay { replay_err } => write!(f, "{replay_err}"),
+            Error::Bot { bot_err } => write!(f, "{bot_err}"),
+        }
+    }
+}
+
+impl ::std::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self {
+            Error::MissingPath
+            | Error::UnknownFlag { .. }
+            | Error::InvalidRound { .. }
+            | Error::RoundNotRecorded { .. } => None,
+            Error::Io { io_err } => Some(io_err),
+            Error::Replay { replay_err } => Some(replay_err),
+            Error::Bot { bot_err } => Some(bot_err),
+        }
+    }
+}
+
+impl From<::std::io::Error> for Error
+{
+    fn from(io_err: ::std::io::Error) -> Self
+    {
+        Error::Io { io_err }
+    }
+}
+
+impl From<replay::Error> for Error
+{
+    fn from(replay_err: replay::Error) -> Self
+    {
+        Error::Replay { replay_err }
+    }
+}
+
+impl From<mars_bot::Error> for Error
+{
+    fn from(bot_err: mars_bot::Error) -> Self
+    {
+        Error::Bot { bot_err }
+    }
+}
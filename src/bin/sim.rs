@@ -0,0 +1,191 @@
+use ::std::collections;
+
+use mars_bot::{self as bot, sim};
+
+fn main()
+{
+    match try_main() {
+        Ok(_) => {}
+        Err(err) => panic!("{err:?}"),
+    }
+}
+
+fn try_main() -> ::std::result::Result<(), Error>
+{
+    let Args { config, out } = parse_args(::std::env::args().skip(1))?;
+
+    let mut simulator = sim::Simulator::try_init(config)?;
+    let mut bots = collections::BTreeMap::<u8, bot::Bot>::new();
+
+    if let Some(ref out) = out {
+        ::std::fs::create_dir_all(format!("{out}/game"))?;
+    }
+
+    while !simulator.is_over() {
+        let round = simulator.round();
+
+        for id in simulator.alive() {
+            // SAFETY: `alive` only yields ids with a view
+            let input = simulator.view(id).unwrap();
+
+            let next_turn = match bots.get_mut(&id) {
+                Some(bot) => bot.turn(&input),
+                None => bot::uninit::try_init(&input).map(|(init_bot, next_turn)| {
+                    let _previous = bots.insert(id, init_bot);
+
+                    next_turn
+                }),
+            };
+
+            if let Some(ref out) = out {
+                ::std::fs::write(format!("{out}/game/s{id}_{round}.txt"), &input)?;
+            }
+
+            match next_turn {
+                Ok(next_turn) => {
+                    if let Some(ref out) = out {
+                        ::std::fs::write(format!("{out}/game/c{id}_{round}.txt"), &next_turn)?;
+                    }
+
+                    if let Err(sim_err) = simulator.try_submit(id, &next_turn) {
+                        eprintln!("round {round}, player {id}: {sim_err}");
+                    }
+                }
+                Err(bot_err) => eprintln!("round {round}, player {id}: {bot_err}"),
+            }
+        }
+
+        simulator.step();
+    }
+
+    println!("finished after {} rounds", simulator.round());
+    for (
+        place,
+        sim::Standing {
+            id,
+            hit_points,
+            eliminated_at,
+        },
+    ) in simulator.standings().into_iter().enumerate()
+    {
+        match eliminated_at {
+            Some(round) => println!("{}. player {id}, eliminated in round {round}", place + 1),
+            None => println!("{}. player {id}, {hit_points} hit points", place + 1),
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Args
+{
+    config: sim::Config,
+    out: Option<String>,
+}
+
+fn parse_args<I>(mut args: I) -> Result<Args, Error>
+where
+    I: Iterator<Item = String>,
+{
+    let mut parsed = Args {
+        config: sim::Config::default(),
+        out: None,
+    };
+
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or(Error::MissingValue { flag: flag.clone() })
+        };
+
+        match flag.as_str() {
+            "--width" => parsed.config.width = value()?.parse()?,
+            "--height" => parsed.config.height = value()?.parse()?,
+            "--players" => parsed.config.players = value()?.parse()?,
+            "--seed" => parsed.config.seed = value()?.parse()?,
+            "--rounds" => parsed.config.rounds = value()?.parse()?,
+            "--out" => parsed.out = Some(value()?),
+            _ => return Err(Error::UnknownFlag { flag }),
+        }
+    }
+
+    Ok(parsed)
+}
+
+#[derive(Debug)]
+enum Error
+{
+    Io
+    {
+        io_err: ::std::io::Error
+    },
+    ParseInt
+    {
+        parse_int_err: ::std::num::ParseIntError,
+    },
+    UnknownFlag
+    {
+        flag: String
+    },
+    MissingValue
+    {
+        flag: String
+    },
+
+    Sim
+    {
+        sim_err: sim::Error
+    },
+}
+
+impl ::std::fmt::Display for Error
+{
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result
+    {
+        match self {
+            Error::Io { io_err } => write!(f, "{io_err}"),
+            Error::ParseInt { parse_int_err } => write!(f, "{parse_int_err}"),
+            Error::UnknownFlag { flag } => write!(f, "unknown flag {flag}"),
+            Error::MissingValue { flag } => write!(f, "missing value for {flag}"),
+            Error::Sim { sim_err } => write!(f, "{sim_err}"),
+        }
+    }
+}
+
+impl ::std::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self {
+            Error::Io { io_err } => Some(io_err),
+            Error::ParseInt { parse_int_err } => Some(parse_int_err),
+            Error::Sim { sim_err } => Some(sim_err),
+            Error::UnknownFlag { .. } | Error::MissingValue { .. } => None,
+        }
+    }
+}
+
+impl From<::std::io::Error> for Error
+{
+    fn from(io_err: ::std::io::Error) -> Self
+    {
+        Error::Io { io_err }
+    }
+}
+
+impl From<::std::num::ParseIntError> for Error
+{
+    fn from(parse_int_err: ::std::num::ParseIntError) -> Self
+    {
+        Error::ParseInt { parse_int_err }
+    }
+}
+
+impl From<sim::Error> for Error
+{
+    fn from(sim_err: sim::Error) -> Self
+    {
+        Error::Sim { sim_err }
+    }
+}
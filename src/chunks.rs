@@ -0,0 +1,113 @@
+use crate::{
+    constants::rules,
+    game::{self, Dimensions},
+    map,
+};
+
+// NOTE: Maps are generated with exactly one Osmium per chunk,
+// so once a chunk's Osmium is known the rest of it holds none
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct Chunks
+{
+    pub(super) dimensions: Dimensions,
+    pub(super) osmium: Box<[Osmium]>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) enum Osmium
+{
+    #[default]
+    Unknown,
+    Found,
+    Mined,
+}
+
+impl Chunks
+{
+    pub(super) fn init(Dimensions { width, height }: Dimensions) -> Self
+    {
+        let dimensions = Dimensions {
+            width: width.div_ceil(rules::CHUNK_SIZE),
+            height: height.div_ceil(rules::CHUNK_SIZE),
+        };
+
+        Chunks {
+            osmium: vec![Osmium::Unknown; dimensions.width * dimensions.height].into(),
+            dimensions,
+        }
+    }
+
+    pub(super) fn update_with(&mut self, map: &map::Map)
+    {
+        for (index, osmium) in self.osmium.iter_mut().enumerate() {
+            let (mut found, mut all_seen) = (false, true);
+
+            for map::Entry {
+                tile, last_seen, ..
+            } in tiles_of(index, self.dimensions, map)
+            {
+                found |= *tile == game::Tile::Osmium;
+                all_seen &= last_seen.is_some();
+            }
+
+            // NOTE: Osmium that was known about and is now gone,
+            // or never showed up in a fully seen chunk, got mined
+            *osmium = match osmium {
+                _ if found => Osmium::Found,
+                Osmium::Found | Osmium::Mined => Osmium::Mined,
+                Osmium::Unknown if all_seen => Osmium::Mined,
+                Osmium::Unknown => Osmium::Unknown,
+            };
+        }
+    }
+
+    pub(super) fn index_of(&self, position: game::Position) -> Option<usize>
+    {
+        let chunk = chunk_of(position);
+
+        chunk
+            .is_within_bounds(self.dimensions)
+            .then(|| chunk.to_linear(self.dimensions.width))
+    }
+
+    pub(super) fn unseen_counts(&self, map: &map::Map) -> Vec<usize>
+    {
+        (0..self.osmium.len())
+            .map(|index| {
+                tiles_of(index, self.dimensions, map)
+                    .filter(|map::Entry { last_seen, .. }| last_seen.is_none())
+                    .count()
+            })
+            .collect()
+    }
+}
+
+fn chunk_of(position: game::Position) -> game::Position
+{
+    game::Position {
+        x: position.x.div_euclid(rules::CHUNK_SIZE as _),
+        y: position.y.div_euclid(rules::CHUNK_SIZE as _),
+    }
+}
+
+fn tiles_of(
+    index: usize,
+    dimensions: Dimensions,
+    map: &map::Map,
+) -> impl Iterator<Item = &map::Entry> + '_
+{
+    let chunk = game::Position::from_linear(index, dimensions.width);
+    let (left, top) = (
+        chunk.x as usize * rules::CHUNK_SIZE,
+        chunk.y as usize * rules::CHUNK_SIZE,
+    );
+
+    (top..(top + rules::CHUNK_SIZE).min(map.dimensions.height)).flat_map(move |y| {
+        let row = y * map.dimensions.width;
+
+        map.entries[row + left..row + (left + rules::CHUNK_SIZE).min(map.dimensions.width)].iter()
+    })
+}
+
+#[cfg(test)]
+mod tests;
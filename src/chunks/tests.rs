@@ -0,0 +1,64 @@
+use super::*;
+
+// NOTE: Fog stands for tiles that are yet to be seen
+fn map(rows: &[&str]) -> map::Map
+{
+    let dimensions = Dimensions {
+        width: rows[0].len(),
+        height: rows.len(),
+    };
+    let entries = rows
+        .iter()
+        .flat_map(|row| row.bytes())
+        .map(|byte| {
+            let mut entry = map::Entry::init(crate::io::input::map::tile::try_parse(byte).unwrap());
+            entry.last_seen = (byte != b'?').then_some(1);
+
+            entry
+        })
+        .collect();
+
+    map::Map::new(dimensions, entries, map::CostModel::default())
+}
+
+#[test]
+fn init_covers_partial_chunks()
+{
+    let chunks = Chunks::init(Dimensions {
+        width: rules::CHUNK_SIZE + 1,
+        height: rules::CHUNK_SIZE,
+    });
+
+    assert_eq!(
+        chunks.dimensions,
+        Dimensions {
+            width: 2,
+            height: 1
+        }
+    );
+    assert_eq!(chunks.index_of(game::Position { x: 4, y: 0 }), Some(0));
+    assert_eq!(chunks.index_of(game::Position { x: 5, y: 4 }), Some(1));
+    assert_eq!(chunks.index_of(game::Position { x: 10, y: 0 }), None);
+    assert_eq!(chunks.index_of(game::Position { x: -1, y: 0 }), None);
+}
+
+#[test]
+fn update_with_follows_each_chunks_osmium()
+{
+    let mut chunks = Chunks::init(Dimensions {
+        width: 7,
+        height: 5,
+    });
+
+    let map = self::map(&["..D..??", ".....??", ".....??", ".....??", "....X.?"]);
+    chunks.update_with(&map);
+    assert_eq!(chunks.osmium[..], [Osmium::Found, Osmium::Unknown]);
+    assert_eq!(chunks.unseen_counts(&map), [0, 9]);
+
+    // NOTE: Gone from the chunk it was seen in, and never
+    // showing up in a chunk that's now been seen in full
+    let map = self::map(&[".......", ".......", ".......", ".......", "....X.."]);
+    chunks.update_with(&map);
+    assert_eq!(chunks.osmium[..], [Osmium::Mined, Osmium::Mined]);
+    assert_eq!(chunks.unseen_counts(&map), [0, 0]);
+}
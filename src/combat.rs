@@ -0,0 +1,227 @@
+use crate::{
+    constants::rules,
+    game::{self, direction},
+    map, opponents,
+};
+
+// NOTE: Where to shoot from this turn, and which way
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct Engagement
+{
+    pub(super) path: Option<map::Path>,
+    pub(super) direction: game::Direction,
+}
+
+// NOTE: Where to run to this turn, and which
+// way to put a block down behind the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct Retreat
+{
+    pub(super) path: Option<map::Path>,
+    pub(super) block: Option<game::Direction>,
+}
+
+// NOTE: Out of the spots reachable without mining this turn, or just
+// the player's own when it can't move, the one with a duel worth
+// taking, preferring ones the target can't shoot back at, then
+// staying put, then the fewest turns away. Opponents are taken
+// to be at full health, as there's no seeing theirs
+pub(super) fn engage(
+    map: &map::Map,
+    opponents: &opponents::Opponents,
+    player: &game::Player,
+    can_move: bool,
+) -> Option<Engagement>
+{
+    let width = map.dimensions.width;
+    let turns = map.turns();
+
+    let mut candidates = spots(map)
+        .filter(|&position| can_move || position == player.position)
+        .flat_map(|position| {
+            opponents
+                .opponents
+                .values()
+                .filter(|opponent| opponent.up_to_date)
+                .filter_map(move |opponent| {
+                    let (direction, distance) = line_of_fire(map, position, opponent)?;
+
+                    (distance <= rules::level_get(&rules::RIFLE_RANGES, player.stats.rifle_level))
+                        .then_some((position, opponent, direction, distance))
+                })
+        })
+        .filter_map(|(position, opponent, direction, distance)| {
+            let exposed =
+                distance <= rules::level_get(&rules::RIFLE_RANGES, opponent.stats.rifle.level);
+
+            (!exposed || wins_duel(player, opponent)).then_some((
+                (
+                    exposed,
+                    position != player.position,
+                    turns[position.to_linear(width)],
+                ),
+                position,
+                opponent.id,
+                direction,
+            ))
+        })
+        .collect::<Vec<_>>();
+    // NOTE: Opponents come in no particular order, so ties
+    // between them are broken by id to keep replays exact
+    candidates.sort_by_key(|(rank, position, id, _)| (*rank, position.to_linear(width), *id));
+
+    candidates
+        .into_iter()
+        .find_map(|(_, position, _, direction)| {
+            reach(map, player, position).map(|path| Engagement { path, direction })
+        })
+}
+
+// NOTE: Once an opponent the player would lose a duel against is close
+// enough to take aim by next turn, the spot out of every such opponent's
+// line of fire, and clear of acid through next turn, that is the furthest
+// from the nearest of them. Whatever the player walks off of last gets
+// blocked, if there's stone for it and it's open ground other than the
+// base, which the map shows as air while the player stands on it
+pub(super) fn retreat(
+    map: &map::Map,
+    opponents: &opponents::Opponents,
+    player: &game::Player,
+    turn: usize,
+) -> Option<Retreat>
+{
+    let width = map.dimensions.width;
+
+    let threats = opponents
+        .opponents
+        .values()
+        .filter(|opponent| opponent.up_to_date)
+        .filter(|opponent| {
+            let reach = rules::level_get(&rules::RIFLE_RANGES, opponent.stats.rifle.level)
+                + opponent.stats.wheel.level as usize;
+
+            player.position.manhattan_distance(&opponent.position) <= reach
+                && !wins_duel(player, opponent)
+        })
+        .collect::<Vec<_>>();
+    if threats.is_empty() {
+        return None;
+    }
+
+    let mut candidates = spots(map)
+        .filter(|&position| !map.is_acid(position, turn + 1))
+        .filter(|&position| {
+            threats.iter().all(|opponent| {
+                line_of_fire(map, position, opponent).is_none_or(|(_, distance)| {
+                    distance > rules::level_get(&rules::RIFLE_RANGES, opponent.stats.rifle.level)
+                })
+            })
+        })
+        .map(|position| {
+            let nearest = threats
+                .iter()
+                .map(|opponent| position.manhattan_distance(&opponent.position))
+                .min()
+                .unwrap_or_default();
+
+            (::core::cmp::Reverse(nearest), position)
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(rank, position)| (*rank, position.to_linear(width)));
+
+    let path = candidates
+        .into_iter()
+        .find_map(|(_, position)| reach(map, player, position))?;
+
+    let block = path
+        .and_then(
+            |map::Path {
+                 moves,
+                 end_position,
+                 ..
+             }| {
+                let behind = moves.mvs.into_iter().flatten().last()?.opposite();
+                let walked_off = end_position + behind;
+
+                (walked_off != player.base && map.tile_at_is(walked_off, game::NonPlayerTile::Air))
+                    .then_some(behind)
+            },
+        )
+        .filter(|_| player.inventory.stone > 0);
+
+    Some(Retreat { path, block })
+}
+
+// NOTE: Every spot reachable this turn, the player's own included
+fn spots(map: &map::Map) -> impl Iterator<Item = game::Position> + '_
+{
+    let width = map.dimensions.width;
+
+    map.turns()
+        .into_vec()
+        .into_iter()
+        .enumerate()
+        .filter(|(_, turns)| matches!(turns, Some(0 | 1)))
+        .map(move |(index, _)| game::Position::from_linear(index, width))
+}
+
+// NOTE: Only a path walked in full, without stopping to mine, gets
+// there this turn. Staying put takes no path at all
+fn reach(
+    map: &map::Map,
+    player: &game::Player,
+    position: game::Position,
+) -> Option<Option<map::Path>>
+{
+    if position == player.position {
+        return Some(None);
+    }
+
+    let path = map.find_path(player.position, position, player.stats.wheel_level)?;
+    (path.end_position == position && path.mine_direction.is_none()).then_some(Some(path))
+}
+
+// NOTE: The direction and distance from `from` to the opponent,
+// as long as nothing but open ground lies between
+fn line_of_fire(
+    map: &map::Map,
+    from: game::Position,
+    opponent: &opponents::Opponent,
+) -> Option<(game::Direction, usize)>
+{
+    let direction = *direction::DIRECTIONS.iter().find(|&&direction| {
+        let step = from + direction;
+        let (step_x, step_y) = (step.x - from.x, step.y - from.y);
+        let (to_x, to_y) = (opponent.position.x - from.x, opponent.position.y - from.y);
+
+        (to_x == 0 || to_y == 0) && to_x.signum() == step_x && to_y.signum() == step_y
+    })?;
+    let distance = from.manhattan_distance(&opponent.position);
+
+    let mut position = from;
+    for _ in 1..distance {
+        position = position + direction;
+
+        match map.tile_at(position)? {
+            game::Tile::Air | game::Tile::Base | game::Tile::Acid => {}
+            _ => return None,
+        }
+    }
+
+    Some((direction, distance))
+}
+
+// NOTE: Shooting first, the player wins if it
+// needs no more hits than the opponent does
+fn wins_duel(player: &game::Player, opponent: &opponents::Opponent) -> bool
+{
+    let hits = |hit_points: u8, rifle_level: u8| {
+        hit_points.div_ceil(rules::level_get(&rules::RIFLE_DAMAGES, rifle_level))
+    };
+
+    hits(rules::MAX_HIT_POINTS, player.stats.rifle_level)
+        <= hits(player.stats.hit_points, opponent.stats.rifle.level)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,182 @@
+use super::*;
+use crate::io::input::map::tile;
+
+const TURN: usize = 10;
+
+// NOTE: `0` stands for the player, on its base unless there's an
+// `E`, and other digits for opponents at the first levels, all in
+// sight. The player carries a stone for blocking
+fn scene(rows: &[&str], wheel_level: u8) -> (map::Map, opponents::Opponents, game::Player)
+{
+    let dimensions = game::Dimensions {
+        width: rows[0].len(),
+        height: rows.len(),
+    };
+
+    let (mut position, mut base) = (None, None);
+    let tiles = rows
+        .iter()
+        .flat_map(|row| row.bytes())
+        .enumerate()
+        .map(|(index, byte)| {
+            let at = game::Position::from_linear(index, dimensions.width);
+
+            match tile::try_parse(byte).unwrap() {
+                game::Tile::Player { id: 0 } => {
+                    position = Some(at);
+
+                    game::Tile::Air
+                }
+                tile => {
+                    if tile == game::Tile::Base {
+                        base = Some(at);
+                    }
+
+                    tile
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let position = position.unwrap();
+
+    let mut opponents = opponents::Opponents::default();
+    opponents.update_with(&tiles, dimensions.width, TURN);
+
+    let entries = tiles
+        .iter()
+        .map(|&tile| {
+            let mut entry = map::Entry::init(tile);
+            entry.last_seen = Some(TURN);
+
+            entry
+        })
+        .collect();
+    let mut map = map::Map::new(dimensions, entries, map::CostModel::Turns);
+    map.flood_fill(
+        position,
+        map::Traveller {
+            wheel_level,
+            drill_level: 1,
+            turn: TURN,
+        },
+    );
+
+    let player = game::Player {
+        position,
+        stats: game::player::Stats {
+            hit_points: rules::MAX_HIT_POINTS,
+
+            drill_level: 1,
+            rifle_level: 1,
+            wheel_level,
+            sight_level: 1,
+
+            has_antenna: false,
+            has_battery: false,
+        },
+        inventory: game::player::Inventory {
+            stone: 1,
+            iron: 0,
+            osmium: 0,
+        },
+
+        base: base.unwrap_or(position),
+    };
+
+    (map, opponents, player)
+}
+
+fn arm(opponents: &mut opponents::Opponents, id: opponents::Id, rifle_level: u8)
+{
+    opponents
+        .opponents
+        .get_mut(&id)
+        .unwrap()
+        .stats
+        .rifle
+        .observe(rifle_level, opponents::Confidence::Certain);
+}
+
+#[test]
+fn engage_shoots_from_where_it_stands_when_it_wins()
+{
+    let (map, opponents, player) = scene(&["..0.1"], 1);
+
+    assert_eq!(
+        engage(&map, &opponents, &player, false),
+        Some(Engagement {
+            path: None,
+            direction: game::Direction::Right,
+        })
+    );
+}
+
+#[test]
+fn engage_leaves_duels_it_would_lose()
+{
+    let (map, mut opponents, player) = scene(&["..0.1"], 1);
+    arm(&mut opponents, 1, 3);
+
+    assert_eq!(engage(&map, &opponents, &player, true), None);
+}
+
+// NOTE: Out of the opponent's range, the player takes
+// aim even when it would lose a duel at closer quarters
+#[test]
+fn engage_steps_into_line_out_of_reach_of_return_fire()
+{
+    let (map, opponents, mut player) = scene(&["0....", ".....", ".....", ".....", ".1..."], 1);
+    player.stats.rifle_level = 2;
+    player.stats.hit_points = 1;
+
+    let Engagement { path, direction } = engage(&map, &opponents, &player, true).unwrap();
+    assert_eq!(
+        path.map(|path| path.end_position),
+        Some(game::Position { x: 1, y: 0 })
+    );
+    assert_eq!(direction, game::Direction::Down);
+
+    assert_eq!(engage(&map, &opponents, &player, false), None, "caged");
+}
+
+#[test]
+fn retreat_stands_up_to_opponents_it_would_beat()
+{
+    let (map, opponents, player) = scene(&["..0.1"], 1);
+
+    assert_eq!(retreat(&map, &opponents, &player, TURN), None);
+}
+
+#[test]
+fn retreat_leaves_the_line_of_fire_and_blocks_the_way_behind()
+{
+    let rows = [".....", ".....", "1.0..", ".....", "....E"];
+    let (map, mut opponents, mut player) = scene(&rows, 1);
+    arm(&mut opponents, 1, 3);
+
+    // NOTE: Both ways out are as far, ties go to the lower index
+    let Retreat { path, block } = retreat(&map, &opponents, &player, TURN).unwrap();
+    assert_eq!(
+        path.map(|path| path.end_position),
+        Some(game::Position { x: 2, y: 1 })
+    );
+    assert_eq!(block, Some(game::Direction::Down));
+
+    player.inventory.stone = 0;
+    assert_eq!(
+        retreat(&map, &opponents, &player, TURN).unwrap().block,
+        None,
+        "no stone"
+    );
+}
+
+#[test]
+fn retreat_never_blocks_off_the_base()
+{
+    let (map, mut opponents, player) = scene(&[".....", ".....", "1.0..", ".....", "....."], 1);
+    arm(&mut opponents, 1, 3);
+
+    let Retreat { path, block } = retreat(&map, &opponents, &player, TURN).unwrap();
+    assert!(path.is_some());
+    assert_eq!(block, None);
+}
@@ -69,4 +69,71 @@ pub(crate) mod acid
 {
     pub(crate) const START_TURN: usize = 150;
     pub(crate) const TICK_RATE: usize = 2;
+
+    // NOTE: In distance units of either cost model, steep
+    // enough to go around acid rather than through it
+    pub(crate) const ARRIVAL_PENALTY: usize = 100;
+    // NOTE: Turns to spare when setting off for the centre, a turn
+    // spent elsewhere can cost one for the wait and one for the detour
+    pub(crate) const DEPARTURE_SLACK: isize = 2;
+}
+
+pub(crate) mod danger
+{
+    // NOTE: In distance units of either cost model, per
+    // opponent with a clear shot at the tile
+    pub(crate) const FIRE_PENALTY: usize = 40;
+    // NOTE: For tiles an opponent could get to first,
+    // as long as it's within the next few turns
+    pub(crate) const CONTESTED_PENALTY: usize = 10;
+    pub(crate) const HORIZON: usize = 3;
+}
+
+pub(crate) mod memory
+{
+    // NOTE: Ores unseen for longer are likely
+    // to have been mined by someone else
+    pub(crate) const MAX_ORE_AGE: usize = 50;
+}
+
+pub(crate) mod trip
+{
+    // NOTE: Every order of the stops gets weighed,
+    // so there can't be too many of them
+    pub(crate) const MAX_STOPS: usize = 8;
+    // NOTE: Ores considered on top of the ones needed,
+    // in case a farther set makes for a shorter trip
+    pub(crate) const SPARE_STOPS: usize = 2;
+    // NOTE: Turns a plan is followed before ores
+    // coming into sight get weighed in
+    pub(crate) const REPLAN_INTERVAL: usize = 5;
+}
+
+pub(crate) mod rules
+{
+    pub(crate) const MAX_HIT_POINTS: u8 = 10;
+    pub(crate) const MAX_WHEEL_LEVEL: u8 = 3;
+
+    pub(crate) const SIGHT_RADII: [usize; 3] = [3, 5, 7];
+    pub(crate) const RIFLE_RANGES: [usize; 3] = [2, 4, 6];
+    pub(crate) const RIFLE_DAMAGES: [u8; 3] = [1, 2, 3];
+    // NOTE: Ores always come out with a single hit,
+    // only stone and cobblestone take longer
+    pub(crate) const STONE_HITS: [u8; 3] = [2, 1, 1];
+
+    pub(crate) const SCAN_RANGE: usize = 8;
+    pub(crate) const ACID_DAMAGE: u8 = 1;
+
+    pub(crate) const CHUNK_SIZE: usize = 5;
+
+    pub(crate) fn level_get<T>(table: &[T], level: u8) -> T
+    where
+        T: Copy,
+    {
+        // NOTE: Levels start at 1, anything above
+        // the table saturates to its last entry
+        let index = (level as usize).saturating_sub(1).min(table.len() - 1);
+
+        table[index]
+    }
 }
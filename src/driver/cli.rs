@@ -0,0 +1,182 @@
+use ::std::path;
+
+use mars_bot as bot;
+
+use crate::driver::{log, transport::socket};
+
+pub(crate) const USAGE: &str = "\
+Usage: mars_bot [OPTIONS] [GAME_DIR]
+
+Options:
+      --id <ID>              Player id, prompted for on stdin when missing
+      --game-dir <DIR>       Folder containing the `game` folder [default: .]
+      --profile <PROFILE>    Path costs, balanced (tile weights), explorer or fighter (turns) [default: balanced]
+      --transport <KIND>     file, stdio, tcp:<HOST:PORT> or unix:<PATH> [default: file]
+      --record <PATH>        Record every turn to a replay file
+      --snapshot <PATH>      Save the bot after every turn and restore it on restart
+      --log-level <LEVEL>    off, error, warn, info or debug [default: warn]
+      --start-round <ROUND>  Round to start waiting for [default: first unanswered]
+  -h, --help                 Print this help";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Transport
+{
+    File,
+    Stdio,
+    Socket
+    {
+        address: socket::Address,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Args
+{
+    pub(crate) id: Option<usize>,
+    pub(crate) game_dir: path::PathBuf,
+    pub(crate) profile: bot::Profile,
+    pub(crate) transport: Transport,
+    pub(crate) record: Option<path::PathBuf>,
+    pub(crate) snapshot: Option<path::PathBuf>,
+    pub(crate) log_level: log::Level,
+    pub(crate) start_round: Option<usize>,
+
+    pub(crate) help: bool,
+}
+
+impl Default for Args
+{
+    fn default() -> Self
+    {
+        Args {
+            id: None,
+            game_dir: ".".into(),
+            profile: bot::Profile::default(),
+            transport: Transport::File,
+            record: None,
+            snapshot: None,
+            log_level: log::Level::default(),
+            start_round: None,
+
+            help: false,
+        }
+    }
+}
+
+pub(crate) fn try_parse<I>(mut args: I) -> ::std::result::Result<Args, Error>
+where
+    I: Iterator<Item = String>,
+{
+    let mut parsed = Args::default();
+    let mut game_dir_given = false;
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_owned(), Some(value.to_owned()))
+            }
+            _ => (arg, None),
+        };
+
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| Error::MissingValue { flag: flag.clone() })
+        };
+        let invalid = |value: String| Error::InvalidValue {
+            flag: flag.clone(),
+            value,
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => parsed.help = true,
+            "--id" => {
+                let id = value()?;
+                parsed.id = Some(id.parse().map_err(|_| invalid(id))?);
+            }
+            "--game-dir" => {
+                parsed.game_dir = value()?.into();
+                game_dir_given = true;
+            }
+            "--profile" => {
+                let profile = value()?;
+                parsed.profile = profile.parse().map_err(|_| invalid(profile))?;
+            }
+            "--transport" => {
+                let transport = value()?;
+                parsed.transport = match transport.as_str() {
+                    "file" => Transport::File,
+                    "stdio" => Transport::Stdio,
+                    address => Transport::Socket {
+                        address: socket::Address::parse(address)
+                            .ok_or_else(|| invalid(transport.clone()))?,
+                    },
+                };
+            }
+            "--record" => parsed.record = Some(value()?.into()),
+            "--snapshot" => parsed.snapshot = Some(value()?.into()),
+            "--log-level" => {
+                let log_level = value()?;
+                parsed.log_level = log_level.parse().map_err(|_| invalid(log_level))?;
+            }
+            "--start-round" => {
+                let start_round = value()?;
+                parsed.start_round = Some(start_round.parse().map_err(|_| invalid(start_round))?);
+            }
+            // NOTE: Launch scripts predating the flags
+            // pass the game directory on its own
+            positional if !positional.starts_with('-') && !game_dir_given => {
+                parsed.game_dir = positional.into();
+                game_dir_given = true;
+            }
+            _ => return Err(Error::UnknownFlag { flag }),
+        }
+    }
+
+    Ok(parsed)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Error
+{
+    UnknownFlag
+    {
+        flag: String
+    },
+    MissingValue
+    {
+        flag: String
+    },
+    InvalidValue
+    {
+        flag: String, value: String
+    },
+}
+
+impl ::std::fmt::Display for Error
+{
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result
+    {
+        match self {
+            Error::UnknownFlag { flag } => write!(f, "unknown argument {flag}"),
+            Error::MissingValue { flag } => write!(f, "missing value for {flag}"),
+            Error::InvalidValue { flag, value } => write!(f, "invalid value {value} for {flag}"),
+        }
+    }
+}
+
+impl ::std::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self {
+            Error::UnknownFlag { .. } | Error::MissingValue { .. } | Error::InvalidValue { .. } => {
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,87 @@
+use super::*;
+
+fn parse(args: &[&str]) -> ::std::result::Result<Args, Error>
+{
+    try_parse(args.iter().map(|&arg| arg.to_owned()))
+}
+
+#[test]
+fn parse_reads_every_documented_flag()
+{
+    assert_eq!(
+        parse(&[
+            "--id",
+            "2",
+            "--game-dir=out/build",
+            "--profile",
+            "fighter",
+            "--transport=tcp:127.0.0.1:4000",
+            "--record",
+            "game.replay",
+            "--snapshot=bot.snapshot",
+            "--log-level",
+            "debug",
+            "--start-round=17",
+        ]),
+        Ok(Args {
+            id: Some(2),
+            game_dir: "out/build".into(),
+            profile: bot::Profile::Fighter,
+            transport: Transport::Socket {
+                address: socket::Address::Tcp {
+                    address: "127.0.0.1:4000".into(),
+                },
+            },
+            record: Some("game.replay".into()),
+            snapshot: Some("bot.snapshot".into()),
+            log_level: log::Level::Debug,
+            start_round: Some(17),
+
+            help: false,
+        })
+    );
+}
+
+#[test]
+fn parse_takes_a_lone_game_dir_and_defaults_the_rest()
+{
+    assert_eq!(parse(&[]), Ok(Args::default()));
+    assert_eq!(
+        parse(&["out/build", "--help"]),
+        Ok(Args {
+            game_dir: "out/build".into(),
+            help: true,
+            ..Args::default()
+        })
+    );
+}
+
+#[test]
+fn parse_reports_bad_arguments()
+{
+    assert_eq!(
+        parse(&["--colour"]),
+        Err(Error::UnknownFlag {
+            flag: "--colour".into()
+        })
+    );
+    assert_eq!(
+        parse(&["--id"]),
+        Err(Error::MissingValue {
+            flag: "--id".into()
+        })
+    );
+    assert_eq!(
+        parse(&["--transport", "pigeon"]),
+        Err(Error::InvalidValue {
+            flag: "--transport".into(),
+            value: "pigeon".into()
+        })
+    );
+    assert_eq!(
+        parse(&["out/build", "elsewhere"]),
+        Err(Error::UnknownFlag {
+            flag: "elsewhere".into()
+        })
+    );
+}
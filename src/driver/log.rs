@@ -0,0 +1,70 @@
+use ::std::sync::atomic;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum Level
+{
+    Off,
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+}
+
+pub(crate) const LEVELS: [Level; 5] = [
+    Level::Off,
+    Level::Error,
+    Level::Warn,
+    Level::Info,
+    Level::Debug,
+];
+
+static LEVEL: atomic::AtomicU8 = atomic::AtomicU8::new(Level::Warn as _);
+
+pub(crate) fn set_level(level: Level)
+{
+    LEVEL.store(level as _, atomic::Ordering::Relaxed);
+}
+
+pub(crate) fn enabled(level: Level) -> bool
+{
+    level != Level::Off && level as u8 <= LEVEL.load(atomic::Ordering::Relaxed)
+}
+
+impl ::std::fmt::Display for Level
+{
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result
+    {
+        match self {
+            Level::Off => write!(f, "off"),
+            Level::Error => write!(f, "error"),
+            Level::Warn => write!(f, "warn"),
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+        }
+    }
+}
+
+impl ::std::str::FromStr for Level
+{
+    type Err = ();
+
+    fn from_str(input: &str) -> ::std::result::Result<Self, Self::Err>
+    {
+        LEVELS
+            .into_iter()
+            .find(|level| level.to_string() == input)
+            .ok_or(())
+    }
+}
+
+// NOTE: Logs go to stderr, stdout may be carrying the protocol
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::driver::log::enabled($level) {
+            eprintln!("[{}] {}", $level, format_args!($($arg)*));
+        }
+    };
+}
+
+pub(crate) use log;
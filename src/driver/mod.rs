@@ -0,0 +1,21 @@
+use ::std::{fs, io, path};
+
+pub(crate) mod cli;
+pub(crate) mod log;
+pub(crate) mod transport;
+pub(crate) mod watch;
+
+// NOTE: Renaming within the same directory is atomic,
+// so readers never see a half-written file
+pub(crate) fn write_atomically<C>(path: &path::Path, contents: C) -> io::Result<()>
+where
+    C: AsRef<[u8]>,
+{
+    let mut temporary_name = ::std::ffi::OsString::from(".");
+    temporary_name.push(path.file_name().unwrap_or_default());
+    temporary_name.push(".tmp");
+    let temporary_path = path.with_file_name(temporary_name);
+
+    fs::write(&temporary_path, contents)?;
+    fs::rename(temporary_path, path)
+}
@@ -0,0 +1,92 @@
+use ::std::{fs, io, path};
+
+use crate::driver::{self, transport, watch};
+
+#[derive(Debug)]
+pub(crate) struct File
+{
+    directory: path::PathBuf,
+    id: usize,
+
+    watcher: watch::Watcher,
+}
+
+impl File
+{
+    pub(crate) fn new<P>(directory: P, id: usize) -> Self
+    where
+        P: Into<path::PathBuf>,
+    {
+        let directory = directory.into();
+
+        File {
+            watcher: watch::Watcher::new(&directory),
+
+            directory,
+            id,
+        }
+    }
+
+    // NOTE: The round after the last answered one, or the last one
+    // if it is still waiting on us, `None` if the game hasn't started
+    pub(crate) fn resume_round(&self) -> io::Result<Option<usize>>
+    {
+        let prefix = format!("s{}_", self.id);
+
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(io_err) if io_err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(io_err) => return Err(io_err),
+        };
+
+        let latest = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix(&prefix)?
+                    .strip_suffix(".txt")?
+                    .parse::<usize>()
+                    .ok()
+            })
+            .max();
+
+        Ok(latest.map(|round| {
+            let answered = self
+                .directory
+                .join(format!("c{}_{round}.txt", self.id))
+                .exists();
+
+            if answered {
+                round + 1
+            } else {
+                round
+            }
+        }))
+    }
+}
+
+impl transport::Transport for File
+{
+    fn receive(&mut self, round: usize) -> io::Result<Option<String>>
+    {
+        let read_path = self
+            .watcher
+            .wait_for(&format!("s{}_{round}.txt", self.id))?;
+
+        fs::read_to_string(read_path).map(Some)
+    }
+
+    fn retry(&mut self, round: usize) -> io::Result<Option<String>>
+    {
+        self.receive(round)
+    }
+
+    fn send(&mut self, round: usize, output: &str) -> io::Result<()>
+    {
+        let write_path = self.directory.join(format!("c{}_{round}.txt", self.id));
+
+        driver::write_atomically(&write_path, output)
+    }
+}
@@ -0,0 +1,20 @@
+use ::std::io;
+
+pub(crate) mod file;
+pub(crate) mod socket;
+pub(crate) mod stream;
+
+pub(crate) trait Transport
+{
+    // NOTE: `None` means the other side is done with us
+    fn receive(&mut self, round: usize) -> io::Result<Option<String>>;
+
+    // NOTE: Transports that can't fetch the same input
+    // twice simply don't offer a second look
+    fn retry(&mut self, _round: usize) -> io::Result<Option<String>>
+    {
+        Ok(None)
+    }
+
+    fn send(&mut self, round: usize, output: &str) -> io::Result<()>;
+}
@@ -0,0 +1,60 @@
+use ::std::{io, net, path};
+
+use crate::driver::transport::{self, stream};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Address
+{
+    Tcp
+    {
+        address: String
+    },
+    #[cfg(unix)]
+    Unix
+    {
+        path: path::PathBuf
+    },
+}
+
+impl Address
+{
+    pub(crate) fn parse(address: &str) -> Option<Self>
+    {
+        if let Some(address) = address.strip_prefix("tcp:") {
+            return Some(Address::Tcp {
+                address: address.into(),
+            });
+        }
+
+        #[cfg(unix)]
+        if let Some(path) = address.strip_prefix("unix:") {
+            return Some(Address::Unix { path: path.into() });
+        }
+
+        None
+    }
+}
+
+// NOTE: Sockets speak the same `END`-delimited
+// protocol as the stdin/stdout transport
+pub(crate) fn connect(address: &Address) -> io::Result<Box<dyn transport::Transport>>
+{
+    match address {
+        Address::Tcp { address } => {
+            let socket = net::TcpStream::connect(address)?;
+            socket.set_nodelay(true)?;
+
+            let reader = io::BufReader::new(socket.try_clone()?);
+
+            Ok(Box::new(stream::Stream::new(reader, socket)))
+        }
+        #[cfg(unix)]
+        Address::Unix { path } => {
+            let socket = ::std::os::unix::net::UnixStream::connect(path)?;
+
+            let reader = io::BufReader::new(socket.try_clone()?);
+
+            Ok(Box::new(stream::Stream::new(reader, socket)))
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use ::std::io;
+
+use crate::driver::transport;
+
+// NOTE: No map row, coordinate or stats line can ever read like this
+pub(crate) const DELIMITER: &str = "END";
+
+#[derive(Debug)]
+pub(crate) struct Stream<R, W>
+{
+    reader: R,
+    writer: W,
+}
+
+impl<R, W> Stream<R, W>
+where
+    R: io::BufRead,
+    W: io::Write,
+{
+    pub(crate) fn new(reader: R, writer: W) -> Self
+    {
+        Stream { reader, writer }
+    }
+}
+
+impl<R, W> transport::Transport for Stream<R, W>
+where
+    R: io::BufRead,
+    W: io::Write,
+{
+    fn receive(&mut self, _round: usize) -> io::Result<Option<String>>
+    {
+        let mut input = String::new();
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+
+            if self.reader.read_line(&mut line)? == 0 {
+                // NOTE: A frame cut short by the end of the stream is
+                // still handed over, the bot reports it as incomplete
+                return Ok(if input.is_empty() { None } else { Some(input) });
+            }
+
+            if line.trim_end() == DELIMITER {
+                return Ok(Some(input));
+            }
+
+            input.push_str(&line);
+        }
+    }
+
+    fn send(&mut self, _round: usize, output: &str) -> io::Result<()>
+    {
+        writeln!(self.writer, "{output}")?;
+        writeln!(self.writer, "{DELIMITER}")?;
+
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,158 @@
+use ::std::{fs, io, path, thread, time};
+
+// NOTE: Only used when we can't tell the file has been closed,
+// i.e. when polling or when it was already there before we looked
+const SETTLE: time::Duration = time::Duration::from_millis(10);
+const POLL_INTERVAL: time::Duration = time::Duration::from_millis(2);
+
+#[derive(Debug)]
+pub(crate) struct Watcher
+{
+    directory: path::PathBuf,
+
+    #[cfg(target_os = "linux")]
+    inotify: Option<inotify::Inotify>,
+}
+
+impl Watcher
+{
+    pub(crate) fn new<P>(directory: P) -> Self
+    where
+        P: Into<path::PathBuf>,
+    {
+        let directory = directory.into();
+
+        Watcher {
+            #[cfg(target_os = "linux")]
+            inotify: inotify::Inotify::try_watch(&directory).ok(),
+
+            directory,
+        }
+    }
+
+    pub(crate) fn wait_for(&mut self, file_name: &str) -> io::Result<path::PathBuf>
+    {
+        let path = self.directory.join(file_name);
+
+        #[cfg(target_os = "linux")]
+        if let Some(ref mut inotify) = self.inotify {
+            // NOTE: The watch is already in place, so checking
+            // afterwards can't miss a file that is written in between
+            if fs::exists(&path).unwrap_or(false) {
+                thread::sleep(SETTLE);
+            } else {
+                inotify.wait_for_close(file_name)?;
+            }
+
+            return Ok(path);
+        }
+
+        while !fs::exists(&path).unwrap_or(false) {
+            thread::sleep(POLL_INTERVAL);
+        }
+        thread::sleep(SETTLE);
+
+        Ok(path)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify
+{
+    use ::std::{ffi, fs, io, os::unix::prelude::*, path};
+
+    #[derive(Debug)]
+    pub(super) struct Inotify
+    {
+        file: fs::File,
+        buffer: Box<[u8]>,
+    }
+
+    impl Inotify
+    {
+        pub(super) fn try_watch(directory: &path::Path) -> io::Result<Self>
+        {
+            let directory = ffi::CString::new(directory.as_os_str().as_bytes())
+                .map_err(|nul_err| io::Error::new(io::ErrorKind::InvalidInput, nul_err))?;
+
+            // SAFETY: Plain syscall, the returned descriptor
+            // is checked before being taken ownership of
+            let fd = unsafe { sys::inotify_init1(sys::IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // SAFETY: `fd` is a freshly opened descriptor nobody else owns
+            let file = unsafe { fs::File::from_raw_fd(fd) };
+
+            // SAFETY: `directory` is a valid NUL-terminated string
+            let wd = unsafe {
+                sys::inotify_add_watch(
+                    file.as_raw_fd(),
+                    directory.as_ptr(),
+                    sys::IN_CLOSE_WRITE | sys::IN_MOVED_TO,
+                )
+            };
+            if wd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Inotify {
+                file,
+                buffer: vec![0; BUFFER_LENGTH].into_boxed_slice(),
+            })
+        }
+
+        pub(super) fn wait_for_close(&mut self, file_name: &str) -> io::Result<()>
+        {
+            use io::Read;
+
+            loop {
+                let read = self.file.read(&mut self.buffer)?;
+
+                let mut events = &self.buffer[..read];
+                while let Some(header) = events.get(..HEADER_LENGTH) {
+                    // SAFETY: The slice is exactly four bytes long
+                    let name_length =
+                        u32::from_ne_bytes(unsafe { header[12..16].try_into().unwrap_unchecked() })
+                            as usize;
+                    let name = events
+                        .get(HEADER_LENGTH..HEADER_LENGTH + name_length)
+                        .unwrap_or_default();
+
+                    // NOTE: Names are padded with NUL bytes
+                    if name.split(|&by| by == 0).next() == Some(file_name.as_bytes()) {
+                        return Ok(());
+                    }
+
+                    events = events
+                        .get(HEADER_LENGTH + name_length..)
+                        .unwrap_or_default();
+                }
+            }
+        }
+    }
+
+    // NOTE: `struct inotify_event` is an i32 watch descriptor followed by
+    // the u32 mask, cookie and name length, then the name itself
+    const HEADER_LENGTH: usize = 16;
+    const BUFFER_LENGTH: usize = 4096;
+
+    mod sys
+    {
+        use ::std::ffi;
+
+        pub(super) const IN_CLOEXEC: ffi::c_int = 0o2_000_000;
+
+        pub(super) const IN_CLOSE_WRITE: u32 = 0x0000_0008;
+        pub(super) const IN_MOVED_TO: u32 = 0x0000_0080;
+
+        extern "C" {
+            pub(super) fn inotify_init1(flags: ffi::c_int) -> ffi::c_int;
+            pub(super) fn inotify_add_watch(
+                fd: ffi::c_int,
+                pathname: *const ffi::c_char,
+                mask: u32,
+            ) -> ffi::c_int;
+        }
+    }
+}
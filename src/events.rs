@@ -0,0 +1,178 @@
+use ::std::collections;
+
+use crate::{game, map, opponents};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Event
+{
+    Mined
+    {
+        position: game::Position,
+        tile: game::Tile,
+    },
+    Placed
+    {
+        position: game::Position
+    },
+    // NOTE: An ore found gone after a while out of sight,
+    // there's no telling who mined it or when
+    Vanished
+    {
+        position: game::Position,
+        tile: game::Tile,
+    },
+    AcidAdvanced
+    {
+        depth: usize
+    },
+    OpponentAppeared
+    {
+        id: opponents::Id,
+        position: game::Position,
+    },
+    OpponentVanished
+    {
+        id: opponents::Id,
+        position: game::Position,
+    },
+}
+
+// NOTE: As much of a map as it takes to
+// tell what changed about it since
+pub(super) type Record = Box<[(game::Tile, Option<usize>)]>;
+
+pub(super) fn record(map: &map::Map) -> Record
+{
+    map.entries
+        .iter()
+        .map(
+            |map::Entry {
+                 tile, last_seen, ..
+             }| (*tile, *last_seen),
+        )
+        .collect()
+}
+
+pub(super) fn diff(
+    before: &[(game::Tile, Option<usize>)],
+    after: &map::Map,
+    turn: usize,
+) -> Box<[Event]>
+{
+    let width = after.dimensions.width;
+    let last_turn = turn.checked_sub(1);
+
+    let mut events = Vec::new();
+    let mut acid_depth = None;
+    let (mut visible_before, mut visible_after) =
+        (collections::BTreeMap::new(), collections::BTreeMap::new());
+
+    for (
+        index,
+        (
+            &(tile_before, seen_before),
+            map::Entry {
+                tile, last_seen, ..
+            },
+        ),
+    ) in before.iter().zip(after.entries.iter()).enumerate()
+    {
+        let position = game::Position::from_linear(index, width);
+
+        // NOTE: Player tiles outlive the player going out of sight,
+        // only the ones seen on the turn itself are still there
+        if let game::Tile::Player { id } = tile_before {
+            if seen_before == last_turn {
+                let _previous = visible_before.insert(id, position);
+            }
+        }
+        if let game::Tile::Player { id } = *tile {
+            if *last_seen == Some(turn) {
+                let _previous = visible_after.insert(id, position);
+            }
+        }
+
+        if *tile == tile_before {
+            continue;
+        }
+
+        let ore = matches!(tile_before, game::Tile::Iron | game::Tile::Osmium);
+        match (tile_before, tile) {
+            (_, game::Tile::Acid) => {
+                let game::Position { x, y } = position;
+                let (x, y) = (x as usize, y as usize);
+                let depth = x
+                    .min(y)
+                    .min(after.dimensions.width - 1 - x)
+                    .min(after.dimensions.height - 1 - y);
+
+                acid_depth = acid_depth.max(Some(depth));
+            }
+            (
+                game::Tile::Stone | game::Tile::Cobblestone | game::Tile::Iron | game::Tile::Osmium,
+                game::Tile::Air,
+            ) => events.push(if ore && seen_before != last_turn {
+                Event::Vanished {
+                    position,
+                    tile: tile_before,
+                }
+            } else {
+                Event::Mined {
+                    position,
+                    tile: tile_before,
+                }
+            }),
+            (game::Tile::Air, game::Tile::Cobblestone) => events.push(Event::Placed { position }),
+            _ => {}
+        }
+    }
+
+    if let Some(depth) = acid_depth {
+        events.push(Event::AcidAdvanced { depth });
+    }
+
+    for (&id, &position) in &visible_after {
+        if !visible_before.contains_key(&id) {
+            events.push(Event::OpponentAppeared { id, position });
+        }
+    }
+    for (&id, &position) in &visible_before {
+        if !visible_after.contains_key(&id) {
+            events.push(Event::OpponentVanished { id, position });
+        }
+    }
+
+    events.into()
+}
+
+impl ::core::fmt::Display for Event
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            Event::Mined {
+                position: game::Position { x, y },
+                tile,
+            } => write!(f, "{tile:?} mined at {x} {y}"),
+            Event::Placed {
+                position: game::Position { x, y },
+            } => write!(f, "block placed at {x} {y}"),
+            Event::Vanished {
+                position: game::Position { x, y },
+                tile,
+            } => write!(f, "{tile:?} gone from {x} {y}"),
+            Event::AcidAdvanced { depth } => write!(f, "acid advanced to depth {depth}"),
+            Event::OpponentAppeared {
+                id,
+                position: game::Position { x, y },
+            } => write!(f, "opponent {id} appeared at {x} {y}"),
+            Event::OpponentVanished {
+                id,
+                position: game::Position { x, y },
+            } => write!(f, "opponent {id} vanished from {x} {y}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
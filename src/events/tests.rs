@@ -0,0 +1,106 @@
+use super::*;
+use crate::io::input;
+
+fn input(rows: &[&str]) -> input::Input
+{
+    input::try_parse(format!(
+        "{} {}\n{}\n2 2\n10 1 1 1 1 0 0\n0 0 0",
+        rows[0].len(),
+        rows.len(),
+        rows.join("\n")
+    ))
+    .unwrap()
+}
+
+// NOTE: Feeds the map every turn's input, starting
+// on turn one, and the events each one brought
+fn events(turns: &[&[&str]]) -> Vec<Box<[Event]>>
+{
+    let first = input(turns[0]);
+    let mut map = map::Map::new(
+        first.dimensions,
+        first
+            .map
+            .tiles
+            .iter()
+            .copied()
+            .map(map::Entry::init)
+            .collect(),
+        map::CostModel::default(),
+    );
+
+    turns
+        .iter()
+        .enumerate()
+        .map(|(index, rows)| {
+            let before = record(&map);
+            map.update_with(&input(rows), &opponents::Opponents::default(), index + 1);
+
+            diff(&before, &map, index + 1)
+        })
+        .collect()
+}
+
+#[test]
+fn diff_tells_what_changed_in_and_out_of_sight()
+{
+    let events = events(&[
+        &["C...D", ".X...", "..0..", "....1", "....."],
+        &["?...D", ".....", "..0A.", ".....", "....."],
+        &["....D", ".....", "..0A.", "1....", "F...."],
+    ]);
+
+    assert_eq!(
+        events[1..],
+        [
+            [
+                Event::Mined {
+                    position: game::Position { x: 1, y: 1 },
+                    tile: game::Tile::Stone,
+                },
+                Event::Placed {
+                    position: game::Position { x: 3, y: 2 },
+                },
+                Event::OpponentVanished {
+                    id: 1,
+                    position: game::Position { x: 4, y: 3 },
+                },
+            ]
+            .into(),
+            [
+                // NOTE: Out of sight when it went
+                Event::Vanished {
+                    position: game::Position { x: 0, y: 0 },
+                    tile: game::Tile::Iron,
+                },
+                Event::AcidAdvanced { depth: 0 },
+                Event::OpponentAppeared {
+                    id: 1,
+                    position: game::Position { x: 0, y: 3 },
+                },
+            ]
+            .into(),
+        ] as [Box<[Event]>; 2]
+    );
+}
+
+// NOTE: The opponent's last known tile stays in the
+// map, but it's only still there while in sight
+#[test]
+fn diff_lets_opponents_go_out_of_sight()
+{
+    let events = events(&[
+        &["....1", ".....", "..0..", ".....", "....."],
+        &["....?", ".....", "..0..", ".....", "....."],
+        &["....?", ".....", "..0..", ".....", "....."],
+    ]);
+
+    assert_eq!(
+        events[1][..],
+        [Event::OpponentVanished {
+            id: 1,
+            position: game::Position { x: 4, y: 0 },
+        }]
+    );
+    assert_eq!(events[2][..], []);
+}
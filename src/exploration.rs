@@ -0,0 +1,84 @@
+use crate::{chunks, constants::rules, game, map};
+
+// NOTE: A fog tile in a chunk still hiding its Osmium is worth its
+// share of that Osmium, any other fog tile is only worth a pittance
+// so that there's still something to explore once all are found
+const OSMIUM_WEIGHT: u64 = 1 << 16;
+const FOG_WEIGHT: u64 = 1;
+
+// NOTE: The tile revealing the most per turn spent getting there
+pub(super) fn target(
+    map: &map::Map,
+    chunks: &chunks::Chunks,
+    sight_level: u8,
+) -> Option<game::Position>
+{
+    let game::Dimensions { width, height } = map.dimensions;
+    let radius = rules::level_get(&rules::SIGHT_RADII, sight_level);
+
+    let unseen = chunks.unseen_counts(map);
+    let weights = map
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(index, map::Entry { tile, .. })| {
+            if *tile != game::Tile::Fog {
+                return 0;
+            }
+
+            match chunks
+                .index_of(game::Position::from_linear(index, width))
+                .filter(|&chunk| chunks.osmium[chunk] == chunks::Osmium::Unknown)
+            {
+                Some(chunk) => OSMIUM_WEIGHT / unseen[chunk].max(1) as u64,
+                None => FOG_WEIGHT,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // NOTE: Summed-area table, so that every sight
+    // window is summed up in constant time
+    let mut sums = vec![0; (width + 1) * (height + 1)];
+    for y in 0..height {
+        for x in 0..width {
+            sums[(y + 1) * (width + 1) + x + 1] = weights[y * width + x]
+                + sums[y * (width + 1) + x + 1]
+                + sums[(y + 1) * (width + 1) + x]
+                - sums[y * (width + 1) + x];
+        }
+    }
+    let revealed = |x: usize, y: usize| {
+        let (left, top) = (x.saturating_sub(radius), y.saturating_sub(radius));
+        let (right, bottom) = ((x + radius + 1).min(width), (y + radius + 1).min(height));
+
+        sums[bottom * (width + 1) + right] + sums[top * (width + 1) + left]
+            - sums[top * (width + 1) + right]
+            - sums[bottom * (width + 1) + left]
+    };
+
+    map.turns()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, turns)| {
+            let turns = (*turns).filter(|&turns| turns > 0)? as u64;
+            let game::Position { x, y } = game::Position::from_linear(index, width);
+            let revealed = revealed(x as _, y as _);
+
+            (revealed > 0).then_some((index, revealed, turns))
+        })
+        // NOTE: Compared as fractions, ties going to the lower index
+        .reduce(|best, candidate| {
+            let (_, best_revealed, best_turns) = best;
+            let (_, revealed, turns) = candidate;
+
+            if revealed * best_turns > best_revealed * turns {
+                candidate
+            } else {
+                best
+            }
+        })
+        .map(|(index, _, _)| game::Position::from_linear(index, width))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,93 @@
+use super::*;
+
+// NOTE: Three chunks side by side, fog stands for tiles
+// that are yet to be seen and `0` for the player
+fn scene(rows: &[&str]) -> (map::Map, chunks::Chunks)
+{
+    let dimensions = game::Dimensions {
+        width: rows[0].len(),
+        height: rows.len(),
+    };
+
+    let mut player = game::Position::default();
+    let entries = rows
+        .iter()
+        .flat_map(|row| row.bytes())
+        .enumerate()
+        .map(|(index, byte)| {
+            let tile = match byte {
+                b'0' => {
+                    player = game::Position::from_linear(index, dimensions.width);
+
+                    game::Tile::Air
+                }
+                byte => crate::io::input::map::tile::try_parse(byte).unwrap(),
+            };
+
+            let mut entry = map::Entry::init(tile);
+            entry.last_seen = (tile != game::Tile::Fog).then_some(1);
+
+            entry
+        })
+        .collect();
+
+    let mut map = map::Map::new(dimensions, entries, map::CostModel::Turns);
+    map.flood_fill(
+        player,
+        map::Traveller {
+            wheel_level: 1,
+            drill_level: 1,
+            turn: 1,
+        },
+    );
+
+    let mut chunks = chunks::Chunks::init(dimensions);
+    chunks.update_with(&map);
+
+    (map, chunks)
+}
+
+// NOTE: Even with more fog left in it
+#[test]
+fn target_passes_over_chunks_with_their_osmium_found()
+{
+    let (map, chunks) = scene(&[
+        "D????..........",
+        "?????.....?????",
+        "?????..0..?????",
+        "?????.....?????",
+        "?????.....?????",
+    ]);
+
+    let target = target(&map, &chunks, 1).unwrap();
+    assert!(target.x > 7, "{target:?}");
+}
+
+#[test]
+fn target_prefers_revealing_more_over_the_nearest_fog()
+{
+    let (map, chunks) = scene(&[
+        "..........?????",
+        "..........?????",
+        "0.?.......?????",
+        "..........?????",
+        "....D.....?????",
+    ]);
+
+    let target = target(&map, &chunks, 1).unwrap();
+    assert!(target.x >= 7, "{target:?}");
+}
+
+#[test]
+fn target_needs_something_left_to_reveal()
+{
+    let (map, chunks) = scene(&[
+        "...............",
+        "...............",
+        "0..............",
+        "...............",
+        "....D..........",
+    ]);
+
+    assert_eq!(target(&map, &chunks, 1), None);
+}
@@ -3,7 +3,6 @@ use crate::game;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum Action
 {
-    #[allow(dead_code)]
     Attack
     {
         direction: game::Direction
@@ -17,7 +16,6 @@ pub(crate) enum Action
     {
         direction: game::Direction
     },
-    #[allow(dead_code)]
     Place
     {
         direction: game::Direction
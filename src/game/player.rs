@@ -24,6 +24,14 @@ impl Player
     }
 
     pub(crate) fn can_afford(&self, upgrade: game::Upgrade) -> bool
+    {
+        self.shortfall(upgrade)
+            .is_some_and(|shortfall| shortfall == upgrade::Cost::default())
+    }
+
+    // NOTE: What's still missing from the inventory
+    // before the upgrade is worth going back to base for
+    pub(crate) fn shortfall(&self, upgrade: game::Upgrade) -> Option<upgrade::Cost>
     {
         use game::Upgrade as U;
 
@@ -47,8 +55,9 @@ impl Player
             U::Heal => Some(constants::upgrade::HEAL_THRESHOLD),
         };
 
-        threshold.is_some_and(|upgrade::Cost { iron, osmium }| {
-            self.inventory.iron >= iron && self.inventory.osmium >= osmium
+        threshold.map(|upgrade::Cost { iron, osmium }| upgrade::Cost {
+            iron: iron.saturating_sub(self.inventory.iron),
+            osmium: osmium.saturating_sub(self.inventory.osmium),
         })
     }
 }
@@ -37,6 +37,11 @@ impl Position
         self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
     }
 
+    pub(crate) fn chebyshev_distance(&self, other: &Position) -> usize
+    {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
     // pub(crate) fn same_axis_distance(&self, other: &Position) -> Option<usize>
     // {
     //     if self.y == other.y {
@@ -24,7 +24,6 @@ pub(crate) enum Tile
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub(crate) enum NonPlayerTile
 {
-    #[allow(dead_code)]
     Air,
     #[allow(dead_code)]
     Base,
@@ -47,13 +46,13 @@ impl NonPlayerTile
 {
     pub(crate) fn is_obstacle(&self) -> bool
     {
-        match self {
+        matches!(
+            self,
             NonPlayerTile::Acid
-            | NonPlayerTile::Bedrock
-            | NonPlayerTile::Cobblestone
-            | NonPlayerTile::Stone => true,
-            _ => false,
-        }
+                | NonPlayerTile::Bedrock
+                | NonPlayerTile::Cobblestone
+                | NonPlayerTile::Stone
+        )
     }
 }
 
@@ -16,7 +16,6 @@ pub(crate) enum Upgrade
 
 impl Upgrade
 {
-    #[allow(dead_code)]
     pub(crate) fn cost(
         &self,
         player::stats::Stats {
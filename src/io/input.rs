@@ -77,6 +77,23 @@ pub enum Error
     Player(player::Error),
 }
 
+impl Error
+{
+    pub(crate) fn is_incomplete(&self) -> bool
+    {
+        match self {
+            Error::Dimensions(dimensions_err) => matches!(
+                dimensions_err,
+                dimensions::Error::Missing | dimensions::Error::MissingDelimiter
+            ),
+            Error::Map(map_err) => {
+                matches!(map_err, map::Error::Missing | map::Error::Incomplete { .. })
+            }
+            Error::Player(player_err) => player_err.is_incomplete(),
+        }
+    }
+}
+
 impl ::core::fmt::Display for Error
 {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
@@ -187,7 +204,7 @@ mod dimensions
     }
 }
 
-mod map
+pub(crate) mod map
 {
     use crate::game;
 
@@ -278,7 +295,7 @@ mod map
 
         use crate::game;
 
-        pub(super) fn try_parse(input: u8) -> ::core::result::Result<game::Tile, Error>
+        pub(crate) fn try_parse(input: u8) -> ::core::result::Result<game::Tile, Error>
         {
             use game::Tile as T;
 
@@ -372,6 +389,27 @@ mod player
         },
     }
 
+    impl Error
+    {
+        pub(super) fn is_incomplete(&self) -> bool
+        {
+            match self {
+                Error::Position { position_err } => matches!(
+                    position_err,
+                    position::Error::Missing | position::Error::MissingDelimiter
+                ),
+                Error::Stats { stats_err } => matches!(
+                    stats_err,
+                    stats::Error::Missing | stats::Error::MissingProperty { .. }
+                ),
+                Error::Inventory { inventory_err } => matches!(
+                    inventory_err,
+                    inventory::Error::Missing | inventory::Error::MissingItem { .. }
+                ),
+            }
+        }
+    }
+
     impl ::core::fmt::Display for Error
     {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
@@ -19,7 +19,7 @@ pub(crate) fn show(
     [
         moves.map(moves::show),
         action.map(action::show),
-        upgrade.map(upgrade::show),
+        upgrade.map(|upgrade| ['B', ' ', upgrade::show(upgrade)].into_iter().collect()),
     ]
     .into_iter()
     .flatten()
@@ -31,7 +31,7 @@ pub(crate) mod direction
 {
     use crate::game;
 
-    pub(super) fn show(direction: game::Direction) -> char
+    pub(crate) fn show(direction: game::Direction) -> char
     {
         use game::Direction as D;
 
@@ -42,6 +42,44 @@ pub(crate) mod direction
             D::Down => 'D',
         }
     }
+
+    pub(crate) fn try_parse(input: &str) -> Option<game::Direction>
+    {
+        use game::Direction as D;
+
+        match input {
+            "R" => Some(D::Right),
+            "U" => Some(D::Up),
+            "L" => Some(D::Left),
+            "D" => Some(D::Down),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) mod tile
+{
+    use crate::game;
+
+    pub(crate) fn show(tile: game::Tile) -> char
+    {
+        use game::Tile as T;
+
+        match tile {
+            T::Air => '.',
+            T::Stone => 'X',
+            T::Cobblestone => 'A',
+            T::Bedrock => 'B',
+            T::Iron => 'C',
+            T::Osmium => 'D',
+            T::Base => 'E',
+            T::Acid => 'F',
+
+            T::Player { id } => (b'0' + id) as _,
+
+            T::Fog => '?',
+        }
+    }
 }
 
 pub(crate) mod moves
@@ -87,15 +125,15 @@ pub(crate) mod action
     }
 }
 
-mod upgrade
+pub(crate) mod upgrade
 {
     use crate::game;
 
-    pub(super) fn show(upgrade: game::Upgrade) -> String
+    pub(crate) fn show(upgrade: game::Upgrade) -> char
     {
         use game::Upgrade as U;
 
-        let upgrade = match upgrade {
+        match upgrade {
             U::Sight => 'S',
             U::Rifle => 'A',
             U::Drill => 'D',
@@ -105,8 +143,24 @@ mod upgrade
             U::Battery => 'B',
 
             U::Heal => 'H',
-        };
+        }
+    }
 
-        ['B', ' ', upgrade].into_iter().collect()
+    pub(crate) fn try_parse(input: &str) -> Option<game::Upgrade>
+    {
+        use game::Upgrade as U;
+
+        match input {
+            "S" => Some(U::Sight),
+            "A" => Some(U::Rifle),
+            "D" => Some(U::Drill),
+            "M" => Some(U::Wheel),
+
+            "R" => Some(U::Antenna),
+            "B" => Some(U::Battery),
+
+            "H" => Some(U::Heal),
+            _ => None,
+        }
     }
 }
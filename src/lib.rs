@@ -1,12 +1,4 @@
-#![feature(
-    array_try_map,
-    error_in_core,
-    inline_const,
-    iter_intersperse,
-    maybe_uninit_array_assume_init,
-    slice_take,
-    try_blocks
-)]
+#![feature(array_try_map, iter_intersperse)]
 #![warn(
     clippy::all,
     explicit_outlives_requirements,
@@ -20,14 +12,30 @@
     variant_size_differences
 )]
 #![allow(clippy::new_without_default)]
+#![cfg_attr(test, feature(test))]
+
+#[cfg(test)]
+extern crate test;
 
 mod constants;
 
 mod game;
 mod io;
 
+mod chunks;
+mod combat;
+mod events;
+mod exploration;
 mod map;
 mod opponents;
+mod trip;
+
+pub mod profile;
+pub mod replay;
+pub mod sim;
+pub mod snapshot;
+
+pub use profile::Profile;
 
 use crate::io::{input, output};
 
@@ -35,12 +43,16 @@ use crate::io::{input, output};
 pub struct Bot
 {
     map: map::Map,
+    chunks: chunks::Chunks,
 
     player: game::Player,
     opponents: opponents::Opponents,
+    events: Box<[events::Event]>,
 
     turn: usize,
+    profile: Profile,
     upgrade_queue_index: usize,
+    trip: trip::Trip,
 
     cage: caging::Cage,
 }
@@ -63,9 +75,31 @@ impl Bot
                 },
         } = input::try_parse(input.as_ref())?;
 
+        let before = events::record(&self.map);
         self.map.update_acid(self.acid_level());
-        self.map.update_with(input);
-        self.opponents.update_with(tiles, width);
+        self.opponents.update_with(tiles, width, self.turn);
+        self.map.update_with(input, &self.opponents, self.turn);
+        self.events = events::diff(&before, &self.map, self.turn);
+        self.chunks.update_with(&self.map);
+
+        // NOTE: Acid takes its share first, whatever's left came from a
+        // rifle. Healing makes up for more than any hit, so it hides them
+        let acid_damage = if self.map.is_acid(position, self.turn) {
+            constants::rules::ACID_DAMAGE
+        } else {
+            0
+        };
+        let damage = self
+            .player
+            .stats
+            .hit_points
+            .saturating_sub(stats.hit_points)
+            .saturating_sub(acid_damage);
+        if damage > 0 {
+            self.opponents
+                .attribute_hit(&self.map, position, damage, self.turn);
+        }
+
         self.player = game::Player {
             position,
             stats,
@@ -73,8 +107,34 @@ impl Bot
 
             ..self.player
         };
+        self.trip.update_with(
+            &self.map,
+            &self.player,
+            self.target_upgrade(),
+            self.traveller(),
+            self.turn.saturating_sub(constants::memory::MAX_ORE_AGE),
+        );
+
+        // NOTE: Once caged there's no stepping out to pick a fight, nor
+        // running from one, only shooting from where the player stands.
+        // Running away from the centre stops as soon as it's headed for
+        let entrance = self.entrance();
+        let departing = self.departing(entrance.position);
+        let uncaged = self.cage.entryway.is_none();
+        let retreat = (!departing)
+            .then(|| combat::retreat(&self.map, &self.opponents, &self.player, self.turn))
+            .flatten();
+        let engagement = retreat
+            .is_none()
+            .then(|| combat::engage(&self.map, &self.opponents, &self.player, uncaged))
+            .flatten();
+        let path = match (retreat, engagement) {
+            (Some(combat::Retreat { path, .. }), _)
+            | (_, Some(combat::Engagement { path, .. })) => path,
+            (None, None) => self.try_move(entrance, departing),
+        };
 
-        let (moves, new_position, mine_direction) = match self.try_move() {
+        let (moves, new_position, mine_direction) = match path {
             Some(map::Path {
                 moves,
                 end_position,
@@ -105,11 +165,14 @@ impl Bot
                     .map(|map::Neighbour { direction, .. }| direction)
             });
 
-        let action = if let Some(direction) = mine_direction {
-            Some(game::Action::Mine { direction })
-        } else {
-            None
-        };
+        let action = retreat
+            .and_then(|combat::Retreat { block, .. }| block)
+            .map(|direction| game::Action::Place { direction })
+            .or_else(|| {
+                engagement
+                    .map(|combat::Engagement { direction, .. }| game::Action::Attack { direction })
+            })
+            .or_else(|| mine_direction.map(|direction| game::Action::Mine { direction }));
 
         let upgrade = self.try_upgrade();
 
@@ -123,24 +186,55 @@ impl Bot
         Ok(output::show(output))
     }
 
-    fn try_move(&mut self) -> Option<map::Path>
+    pub fn turns_played(&self) -> usize
     {
-        if self.acid_level() > 0 {
-            let center = self.map.center();
+        self.turn
+    }
 
-            let map::Neighbour {
-                direction: center_direction,
-                position: entry,
-            } = self
-                .map
-                .neighbours(center)
-                .iter()
-                .min_by_key(|map::Neighbour { position, .. }| self.map.distance_to(*position))
-                .copied()
-                // (UN)SAFETY: In most cases, the map
-                // won't be just the centre tile
-                .unwrap();
+    // NOTE: What changed about the world over the last turn
+    pub fn events(&self) -> impl Iterator<Item = impl ::core::fmt::Display + '_> + '_
+    {
+        self.events.iter()
+    }
 
+    // NOTE: The centre's neighbour nearest to the
+    // player, where the cage gets entered from
+    fn entrance(&self) -> map::Neighbour
+    {
+        self.map
+            .neighbours(self.map.center())
+            .iter()
+            .min_by_key(|map::Neighbour { position, .. }| self.map.distance_to(*position))
+            .copied()
+            // (UN)SAFETY: In most cases, the map
+            // won't be just the centre tile
+            .unwrap()
+    }
+
+    // NOTE: Whether the player is headed for the centre, which
+    // it is for good once acid is on its way or already there
+    fn departing(&self, entry: game::Position) -> bool
+    {
+        let must_leave = self
+            .map
+            .acid_slack(self.player.position, entry, self.traveller())
+            .is_some_and(|slack| slack <= constants::acid::DEPARTURE_SLACK);
+
+        self.acid_level() > 0 || self.cage.entryway.is_some() || must_leave
+    }
+
+    fn try_move(
+        &mut self,
+        map::Neighbour {
+            direction: center_direction,
+            position: entry,
+        }: map::Neighbour,
+        departing: bool,
+    ) -> Option<map::Path>
+    {
+        let center = self.map.center();
+
+        if departing {
             if entry == self.player.position && self.cage.entryway.is_none() {
                 self.cage.entryway = Some(caging::Entryway {
                     center_direction,
@@ -233,11 +327,26 @@ impl Bot
             );
         }
 
+        if let Some(stop) = self.trip.next_stop() {
+            return self
+                .map
+                .find_path(self.player.position, stop, self.player.stats.wheel_level);
+        }
+
+        // NOTE: Stale ores are only worth a trip
+        // once there's nothing left to explore
+        let seen_since = self.turn.saturating_sub(constants::memory::MAX_ORE_AGE);
         let nearest = self
             .map
-            .nearest_tile(game::NonPlayerTile::Osmium)
-            .or_else(|| self.map.nearest_tile(game::NonPlayerTile::Iron))
-            .or_else(|| self.map.nearest_tile(game::NonPlayerTile::Fog));
+            .nearest_tile(game::NonPlayerTile::Osmium, Some(seen_since))
+            .or_else(|| {
+                self.map
+                    .nearest_tile(game::NonPlayerTile::Iron, Some(seen_since))
+            })
+            .or_else(|| exploration::target(&self.map, &self.chunks, self.player.stats.sight_level))
+            .or_else(|| self.map.nearest_tile(game::NonPlayerTile::Fog, None))
+            .or_else(|| self.map.nearest_tile(game::NonPlayerTile::Osmium, None))
+            .or_else(|| self.map.nearest_tile(game::NonPlayerTile::Iron, None));
 
         self.map.find_path(
             self.player.position,
@@ -290,6 +399,15 @@ impl Bot
         }
     }
 
+    fn traveller(&self) -> map::Traveller
+    {
+        map::Traveller {
+            wheel_level: self.player.stats.wheel_level,
+            drill_level: self.player.stats.drill_level,
+            turn: self.turn,
+        }
+    }
+
     fn acid_level(&self) -> usize
     {
         if self.turn >= constants::acid::START_TURN {
@@ -309,6 +427,18 @@ pub enum Error
     },
 }
 
+impl Error
+{
+    // NOTE: Incomplete inputs are usually files
+    // the game hasn't finished writing yet
+    pub fn is_incomplete(&self) -> bool
+    {
+        match self {
+            Error::Input { input_err } => input_err.is_incomplete(),
+        }
+    }
+}
+
 impl ::core::fmt::Display for Error
 {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
@@ -360,9 +490,34 @@ pub mod uninit
 {
     use std::collections;
 
-    use crate::{caging, game, io::input, map, opponents};
+    use crate::{caging, chunks, game, io::input, map, opponents, trip};
 
     pub fn try_init<In>(input: In) -> ::core::result::Result<(crate::Bot, String), crate::Error>
+    where
+        In: AsRef<str>,
+    {
+        try_init_with_profile(input, crate::Profile::default())
+    }
+
+    pub fn try_init_with_profile<In>(
+        input: In,
+        profile: crate::Profile,
+    ) -> ::core::result::Result<(crate::Bot, String), crate::Error>
+    where
+        In: AsRef<str>,
+    {
+        try_resume(input, profile, 0, None)
+    }
+
+    // NOTE: A bot joining a game `turn` rounds in. The base is taken
+    // from an outdated bot if there is one, otherwise from wherever
+    // the player stands, which only holds on the very first turn
+    pub fn try_resume<In>(
+        input: In,
+        profile: crate::Profile,
+        turn: usize,
+        outdated: Option<&crate::Bot>,
+    ) -> ::core::result::Result<(crate::Bot, String), crate::Error>
     where
         In: AsRef<str>,
     {
@@ -378,25 +533,25 @@ pub mod uninit
                 },
         } = input::try_parse(input.as_ref())?;
 
-        let entries = tiles.iter().copied().map(map::Entry::init).collect();
-        let mut map = map::Map {
-            dimensions,
-            entries,
+        let mut opponents = opponents::Opponents {
+            opponents: collections::HashMap::new(),
         };
-        map.update_with(parsed_input);
+        opponents.update_with(tiles, dimensions.width, turn);
+
+        let entries = tiles.iter().copied().map(map::Entry::init).collect();
+        let mut map = map::Map::new(dimensions, entries, profile.cost_model());
+        map.update_with(parsed_input, &opponents, turn);
+
+        let mut chunks = chunks::Chunks::init(dimensions);
+        chunks.update_with(&map);
 
         let player = game::Player {
             position,
             stats,
             inventory,
 
-            base: position,
-        };
-
-        let mut opponents = opponents::Opponents {
-            opponents: collections::HashMap::new(),
+            base: outdated.map_or(position, |outdated| outdated.player.base),
         };
-        opponents.update_with(tiles, dimensions.width);
 
         let cage = caging::Cage {
             entryway: None,
@@ -405,11 +560,16 @@ pub mod uninit
 
         let mut bot = crate::Bot {
             map,
+            chunks,
+
             player,
             opponents,
+            events: Box::default(),
 
-            turn: 0,
-            upgrade_queue_index: 0,
+            turn,
+            profile,
+            upgrade_queue_index: upgrades_taken(&stats),
+            trip: trip::Trip::default(),
 
             cage,
         };
@@ -418,4 +578,32 @@ pub mod uninit
 
         Ok((bot, first_turn))
     }
+
+    // NOTE: How far down the queue the player's upgrades already go,
+    // the queue is followed in order so only leading entries count
+    fn upgrades_taken(stats: &game::player::stats::Stats) -> usize
+    {
+        let mut levels = collections::HashMap::<game::Upgrade, u8>::new();
+
+        crate::constants::upgrade::QUEUE
+            .iter()
+            .take_while(|&&upgrade| {
+                let level = levels.entry(upgrade).or_insert(1);
+                *level += 1;
+
+                match upgrade {
+                    game::Upgrade::Sight => stats.sight_level >= *level,
+                    game::Upgrade::Rifle => stats.rifle_level >= *level,
+                    game::Upgrade::Drill => stats.drill_level >= *level,
+                    game::Upgrade::Wheel => stats.wheel_level >= *level,
+                    game::Upgrade::Antenna => stats.has_antenna,
+                    game::Upgrade::Battery => stats.has_battery,
+                    game::Upgrade::Heal => false,
+                }
+            })
+            .count()
+    }
 }
+
+#[cfg(test)]
+mod tests;
@@ -1,6 +1,12 @@
-#![feature(fs_try_exists)]
+use ::std::io::Write;
 
-use mars_bot as bot;
+use mars_bot::{self as bot, replay};
+
+use crate::driver::{log::log, transport::Transport};
+
+mod driver;
+
+const INCOMPLETE_INPUT_DEADLINE: ::std::time::Duration = ::std::time::Duration::from_millis(500);
 
 fn main()
 {
@@ -12,44 +18,214 @@ fn main()
 
 fn try_main() -> ::std::result::Result<(), Error>
 {
-    let id = parse_id(::std::io::stdin() /*, ::std::io::stdout()*/)?;
-    let mut round = 0usize;
+    let driver::cli::Args {
+        id,
+        game_dir,
+        profile,
+        transport,
+        record,
+        snapshot,
+        log_level,
+        start_round,
+
+        help,
+    } = driver::cli::try_parse(::std::env::args().skip(1))?;
+
+    if help {
+        println!("{}", driver::cli::USAGE);
+
+        return Ok(());
+    }
+
+    driver::log::set_level(log_level);
+
+    // NOTE: Only the file transport has a use for the id,
+    // stdin is taken by the protocol with `stdio` anyway
+    let (id, resume_round, mut transport) = match transport {
+        driver::cli::Transport::File => {
+            let id = match id {
+                Some(id) => id,
+                None => parse_id(::std::io::stdin() /*, ::std::io::stdout()*/)?,
+            };
+            let transport = driver::transport::file::File::new(game_dir.join("game"), id);
+
+            (
+                id,
+                transport.resume_round()?,
+                Box::new(transport) as Box<dyn Transport>,
+            )
+        }
+        driver::cli::Transport::Stdio => {
+            let transport = driver::transport::stream::Stream::new(
+                ::std::io::stdin().lock(),
+                ::std::io::stdout().lock(),
+            );
+
+            (
+                id.unwrap_or_default(),
+                None,
+                Box::new(transport) as Box<dyn Transport>,
+            )
+        }
+        driver::cli::Transport::Socket { ref address } => (
+            id.unwrap_or_default(),
+            None,
+            driver::transport::socket::connect(address)?,
+        ),
+    };
+
+    let mut round = start_round.or(resume_round).unwrap_or_default();
 
-    let directory_path = ::std::env::args()
-        .nth(1)
-        .unwrap_or_else(|| String::from("."));
+    // NOTE: The bot keeps time by the turns it has played, a snapshot
+    // from any other round would throw it off. Its base still holds
+    let mut outdated = match snapshot {
+        Some(ref snapshot_path) => restore(snapshot_path)?,
+        None => None,
+    };
+    let mut bot = outdated.take_if(|restored| restored.turns_played() == round);
 
-    let mut bot = None::<bot::Bot>;
+    match (&bot, &outdated) {
+        (Some(_), _) => log!(
+            driver::log::Level::Info,
+            "restored the bot at round {round}"
+        ),
+        (None, Some(outdated)) => log!(
+            driver::log::Level::Warn,
+            "ignoring snapshot taken after {} turns while resuming at round {round}, \
+             keeping only its base",
+            outdated.turns_played()
+        ),
+        (None, None) if round > 0 => log!(
+            driver::log::Level::Warn,
+            "resuming at round {round} without a snapshot, \
+             taking the current position for the base"
+        ),
+        (None, None) => {}
+    }
 
-    loop {
-        let read_path = format!("{directory_path}/game/s{id}_{round}.txt");
+    log!(
+        driver::log::Level::Info,
+        "playing as {id} with the {profile} profile from round {round}"
+    );
 
-        let exists = ::std::fs::try_exists(&read_path).unwrap_or(false);
+    // NOTE: A resumed game keeps adding to its recording, marking
+    // where the bot got started afresh so that replays do the same
+    let (mut recording, mut restart) = match record {
+        Some(record_path) if round > 0 && record_path.exists() => (
+            Some(
+                ::std::fs::OpenOptions::new()
+                    .append(true)
+                    .open(record_path)?,
+            ),
+            bot.is_none(),
+        ),
+        Some(record_path) => {
+            let mut recording = ::std::fs::File::create(record_path)?;
+            recording
+                .write_all(replay::show_header(&replay::Header::new(id, profile)).as_bytes())?;
 
-        if exists {
-            ::std::thread::sleep(::std::time::Duration::from_millis(10));
-        } else {
-            continue;
+            (Some(recording), false)
         }
+        None => (None, false),
+    };
 
-        let input = ::std::fs::read_to_string(&read_path)?;
-        let write_path = format!("{directory_path}/game/c{id}_{round}.txt");
+    while let Some(mut input) = transport.receive(round)? {
+        let deadline = ::std::time::Instant::now() + INCOMPLETE_INPUT_DEADLINE;
 
-        let next_turn = match bot {
-            Some(ref mut bot) => bot.turn(&input)?,
-            None => {
-                let (init_bot, next_turn) = bot::uninit::try_init(input)?;
+        let (next_turn, elapsed) = loop {
+            let started = ::std::time::Instant::now();
 
-                let _ = bot.insert(init_bot);
+            match play(&mut bot, &input, profile, round, outdated.as_ref()) {
+                Err(bot_err)
+                    if bot_err.is_incomplete() && ::std::time::Instant::now() < deadline =>
+                {
+                    log!(
+                        driver::log::Level::Warn,
+                        "round {round}: {bot_err}, retrying"
+                    );
 
-                next_turn
+                    match transport.retry(round)? {
+                        Some(retried_input) => input = retried_input,
+                        None => return Err(bot_err.into()),
+                    }
+                }
+                next_turn => break (next_turn?, started.elapsed()),
             }
         };
 
-        ::std::fs::write(write_path, next_turn)?;
+        if let Some(ref mut recording) = recording {
+            let turn = replay::Turn {
+                round,
+                elapsed,
+                restart,
+
+                input,
+                output: next_turn.clone(),
+            };
+
+            recording.write_all(replay::show_turn(&turn).as_bytes())?;
+            restart = false;
+        }
+
+        log!(driver::log::Level::Debug, "round {round} took {elapsed:?}");
+        if let Some(ref bot) = bot {
+            for event in bot.events() {
+                log!(driver::log::Level::Debug, "round {round}: {event}");
+            }
+        }
+
+        transport.send(round, &next_turn)?;
+
+        if let (Some(ref snapshot_path), Some(ref bot)) = (&snapshot, &bot) {
+            driver::write_atomically(snapshot_path, bot::snapshot::show(bot))?;
+        }
 
         round += 1;
     }
+
+    Ok(())
+}
+
+// NOTE: A snapshot that doesn't parse is no worse than a missing
+// one, the game goes on with a fresh bot either way
+fn restore(snapshot_path: &::std::path::Path) -> Result<Option<bot::Bot>, Error>
+{
+    if !snapshot_path.exists() {
+        return Ok(None);
+    }
+
+    match bot::snapshot::try_parse(::std::fs::read_to_string(snapshot_path)?) {
+        Ok(restored) => Ok(Some(restored)),
+        Err(snapshot_err) => {
+            log!(
+                driver::log::Level::Warn,
+                "ignoring snapshot {}: {snapshot_err}",
+                snapshot_path.display()
+            );
+
+            Ok(None)
+        }
+    }
+}
+
+fn play(
+    bot: &mut Option<bot::Bot>,
+    input: &str,
+    profile: bot::Profile,
+    round: usize,
+    outdated: Option<&bot::Bot>,
+) -> Result<String, bot::Error>
+{
+    match bot {
+        Some(ref mut bot) => bot.turn(input),
+        None => {
+            let (init_bot, next_turn) = bot::uninit::try_resume(input, profile, round, outdated)?;
+
+            let _ = bot.insert(init_bot);
+
+            Ok(next_turn)
+        }
+    }
 }
 
 fn parse_id(stdin: ::std::io::Stdin /*, stdout: ::std::io::Stdout*/) -> Result<usize, Error>
@@ -81,6 +257,10 @@ enum Error
     {
         parse_int_err: ::std::num::ParseIntError,
     },
+    Cli
+    {
+        cli_err: driver::cli::Error
+    },
 
     Bot
     {
@@ -95,6 +275,7 @@ impl ::std::fmt::Display for Error
         match self {
             Error::Io { io_err } => write!(f, "{io_err}"),
             Error::ParseInt { parse_int_err } => write!(f, "{parse_int_err}"),
+            Error::Cli { cli_err } => write!(f, "{cli_err}"),
             Error::Bot { bot_err } => write!(f, "{bot_err}"),
         }
     }
@@ -107,6 +288,7 @@ impl ::std::error::Error for Error
         match self {
             Error::Io { io_err } => Some(io_err),
             Error::ParseInt { parse_int_err } => Some(parse_int_err),
+            Error::Cli { cli_err } => Some(cli_err),
             Error::Bot { bot_err } => Some(bot_err),
         }
     }
@@ -128,6 +310,14 @@ impl From<::std::num::ParseIntError> for Error
     }
 }
 
+impl From<driver::cli::Error> for Error
+{
+    fn from(cli_err: driver::cli::Error) -> Self
+    {
+        Error::Cli { cli_err }
+    }
+}
+
 impl From<bot::Error> for Error
 {
     fn from(bot_err: bot::Error) -> Self
@@ -1,8 +1,10 @@
-use ::std::collections;
+use ::std::{cmp, collections};
 
 use crate::{
+    constants::{acid, danger, rules},
     game::{self, direction, player, tile},
     io::{input, output::moves},
+    opponents,
 };
 
 pub(super) use crate::game::Dimensions;
@@ -12,10 +14,63 @@ pub(super) struct Map
 {
     pub(super) dimensions: Dimensions,
     pub(super) entries: Box<[Entry]>,
+    pub(super) cost_model: CostModel,
+
+    acid_from: Box<[usize]>,
+    danger: Box<[Danger]>,
+}
+
+// NOTE: Everything about the player that
+// goes into how long a trip takes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(super) struct Traveller
+{
+    pub(super) wheel_level: u8,
+    pub(super) drill_level: u8,
+    pub(super) turn: usize,
+}
+
+// NOTE: How exposed a tile is to the opponents in sight
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(super) struct Danger
+{
+    // NOTE: Opponents with a clear shot at the tile from where they stand
+    pub(super) fire: u8,
+    // NOTE: The fewest turns any of them could get there
+    // in, as if nothing stood in the way
+    pub(super) reach: Option<usize>,
 }
 
 impl Map
 {
+    pub(super) fn new(dimensions: Dimensions, entries: Box<[Entry]>, cost_model: CostModel)
+        -> Self
+    {
+        // NOTE: Acid closes in one ring at a time from the border,
+        // so each tile turns at a fixed turn known from the start
+        let acid_from = (0..dimensions.width * dimensions.height)
+            .map(|index| {
+                let game::Position { x, y } = game::Position::from_linear(index, dimensions.width);
+                let (x, y) = (x as usize, y as usize);
+                let depth = x
+                    .min(y)
+                    .min(dimensions.width - 1 - x)
+                    .min(dimensions.height - 1 - y);
+
+                acid::START_TURN + depth * acid::TICK_RATE
+            })
+            .collect();
+
+        Map {
+            dimensions,
+            entries,
+            cost_model,
+
+            acid_from,
+            danger: vec![Danger::default(); dimensions.width * dimensions.height].into(),
+        }
+    }
+
     pub(super) fn update_with(
         &mut self,
         input::Input {
@@ -24,10 +79,17 @@ impl Map
             player:
                 game::Player {
                     position,
-                    stats: player::stats::Stats { wheel_level, .. },
+                    stats:
+                        player::stats::Stats {
+                            drill_level,
+                            wheel_level,
+                            ..
+                        },
                     ..
                 },
         }: &input::Input,
+        opponents: &opponents::Opponents,
+        turn: usize,
     )
     {
         // TODO: Proper input validation
@@ -41,113 +103,150 @@ impl Map
             "new map length doesn't coincide with the current map length"
         );
 
-        for (index, Entry { ref mut tile, .. }) in self.entries.iter_mut().enumerate() {
+        for (
+            index,
+            Entry {
+                ref mut tile,
+                ref mut last_seen,
+                ..
+            },
+        ) in self.entries.iter_mut().enumerate()
+        {
             // SAFETY: It has already been checked that
             // the incoming map's lengh matches
             // the current map's length
             let incoming_tile = *(unsafe { tiles.get_unchecked(index) });
 
-            *tile = match incoming_tile {
-                game::Tile::Fog => *tile,
-                _ => incoming_tile,
-            };
+            if incoming_tile != game::Tile::Fog {
+                *tile = incoming_tile;
+                *last_seen = Some(turn);
+            }
         }
 
-        self.flood_fill(*position, *wheel_level);
+        self.update_danger(opponents);
+        self.flood_fill(
+            *position,
+            Traveller {
+                wheel_level: *wheel_level,
+                drill_level: *drill_level,
+                turn,
+            },
+        );
     }
 
-    pub(super) fn flood_fill(&mut self, player: game::Position, wheel_level: u8)
+    pub(super) fn flood_fill(&mut self, player: game::Position, traveller: Traveller)
     {
-        let mut queue = collections::HashSet::new();
-
-        for x in 0..self.dimensions.width as _ {
-            for y in 0..self.dimensions.height as _ {
-                let position = game::Position { x, y };
-                let _newly_inserted = queue.insert(position);
-
-                // SAFETY: Since both our x and y are
-                // within bounds, this entry exists
-                let entry @ &mut Entry { tile, .. } =
-                    unsafe { self.entry_at_unchecked_mut(position) };
-
-                *entry = Entry {
-                    tile,
-                    ..Default::default()
-                };
-            }
+        for entry @ &mut Entry {
+            tile, last_seen, ..
+        } in self.entries.iter_mut()
+        {
+            *entry = Entry {
+                tile,
+                last_seen,
+                ..Default::default()
+            };
         }
 
+        let mut visited = vec![false; self.entries.len()];
+        // NOTE: Ties are broken by linear index, so that equal
+        // maps always flood-fill into equal parent trees
+        let mut queue = collections::BinaryHeap::new();
+
         // SAFETY: The player position should never
         // be in an invalid state, if it is then
         // we've got bigger issues than flood-filling
         let source = unsafe { self.entry_at_unchecked_mut(player) };
         source.distance = 0;
+        queue.push(cmp::Reverse((0, player.to_linear(self.dimensions.width))));
 
-        while !queue.is_empty() {
-            let nearest_position = queue
-                .iter()
-                .copied()
-                // SAFETY: See the safety note where entries are initialised
-                .min_by_key(|&position| unsafe { self.entry_at_unchecked(position) }.distance)
-                // SAFETY: The queue is not empty therefore we always have a minimum
-                .unwrap();
-
-            let _was_present = queue.remove(&nearest_position);
-
-            // SAFETY: See the safety note where entries are initialised
-            let Entry {
-                parent_data,
-                distance,
-                ..
-            } = unsafe { self.entry_at_unchecked(nearest_position) }.clone();
-            let turn_move_index = parent_data.map_or(0, |parent| {
-                (parent.turn_move_index + 1) % (wheel_level as usize)
-            });
+        self.settle(&mut visited, &mut queue, traveller);
+    }
 
-            for Neighbour {
-                direction,
-                position,
-            } in self
-                .neighbours(nearest_position)
-                .into_iter()
-                .filter(|Neighbour { position, .. }| queue.contains(position))
-            {
-                if !position.is_within_bounds(self.dimensions) {
-                    continue;
-                }
-                // SAFETY: Since the position is within bounds
-                // this entry always exists
-                let entry = unsafe { self.entry_at_unchecked_mut(position) };
+    fn settle(
+        &mut self,
+        visited: &mut [bool],
+        queue: &mut collections::BinaryHeap<cmp::Reverse<(usize, usize)>>,
+        traveller: Traveller,
+    )
+    {
+        while let Some(cmp::Reverse((_, nearest_index))) = queue.pop() {
+            // NOTE: Entries are pushed again whenever their distance
+            // improves, only the first pop of each one counts
+            if ::core::mem::replace(&mut visited[nearest_index], true) {
+                continue;
+            }
 
-                let first_move = parent_data.is_none();
+            self.relax_neighbours(
+                game::Position::from_linear(nearest_index, self.dimensions.width),
+                visited,
+                queue,
+                traveller,
+            );
+        }
+    }
 
-                if let Some((weight, requires_mining)) = game::ViableTile::try_from(entry.tile)
-                    .ok()
-                    .map(|viable_tile| (viable_tile.weight(), viable_tile.requires_mining()))
-                {
-                    let alternative = distance
-                        + if requires_mining && first_move {
-                            1000
-                        } else if requires_mining && turn_move_index == 0 {
-                            1
-                        } else {
-                            weight
-                        };
-
-                    if alternative < entry.distance {
-                        *entry = Entry {
-                            distance: alternative,
-                            parent_data: Some(ParentData {
-                                direction_from_parent: direction,
-                                parent_location: nearest_position,
-                                requires_mining,
-
-                                turn_move_index: if requires_mining { 0 } else { turn_move_index },
-                            }),
-
-                            ..*entry
-                        };
-                    }
+    fn relax_neighbours(
+        &mut self,
+        nearest_position: game::Position,
+        visited: &[bool],
+        queue: &mut collections::BinaryHeap<cmp::Reverse<(usize, usize)>>,
+        traveller: Traveller,
+    )
+    {
+        // SAFETY: Only in-bounds positions are ever queued
+        let Entry {
+            parent_data,
+            distance,
+            ..
+        } = unsafe { self.entry_at_unchecked(nearest_position) }.clone();
+        let cost_model = self.cost_model;
+
+        for Neighbour {
+            direction,
+            position,
+        } in self.neighbours(nearest_position)
+        {
+            if !position.is_within_bounds(self.dimensions) {
+                continue;
+            }
+
+            let index = position.to_linear(self.dimensions.width);
+            if visited[index] {
+                continue;
+            }
+
+            let (acid_from, tile_danger) = (self.acid_from[index], self.danger[index]);
+            // SAFETY: Since the position is within bounds
+            // this entry always exists
+            let entry = unsafe { self.entry_at_unchecked_mut(position) };
+
+            if let Ok(viable_tile) = game::ViableTile::try_from(entry.tile) {
+                let requires_mining = viable_tile.requires_mining();
+                let (alternative, turn_move_index, turns) = cost_model.step(
+                    distance,
+                    parent_data,
+                    viable_tile,
+                    traveller,
+                    acid_from,
+                    tile_danger,
+                );
+
+                if alternative < entry.distance {
+                    *entry = Entry {
+                        distance: alternative,
+                        parent_data: Some(ParentData {
+                            direction_from_parent: direction,
+                            parent_location: nearest_position,
+                            requires_mining,
+
+                            turn_move_index,
+                            turns,
+                        }),
+
+                        ..*entry
+                    };
+
+                    queue.push(cmp::Reverse((alternative, index)));
                 }
             }
         }
@@ -155,27 +254,69 @@ impl Map
 
     pub(super) fn update_acid(&mut self, level: usize)
     {
-        let (level, width, height) = (
-            level as _,
-            self.dimensions.width as _,
-            self.dimensions.height as _,
-        );
+        let acid_from = acid::START_TURN + level * acid::TICK_RATE;
+
+        for (entry, _) in self
+            .entries
+            .iter_mut()
+            .zip(&self.acid_from)
+            .filter(|(_, tile_acid_from)| **tile_acid_from < acid_from)
+        {
+            entry.tile = game::Tile::Acid;
+        }
+    }
+
+    // NOTE: Shots stop at the first robot in the way, and fog
+    // is taken to be open ground as there's no telling
+    pub(super) fn update_danger(&mut self, opponents: &opponents::Opponents)
+    {
+        let width = self.dimensions.width;
+        let mut layer = vec![Danger::default(); self.entries.len()];
+
+        for opponent in opponents
+            .opponents
+            .values()
+            .filter(|opponent| opponent.up_to_date)
+        {
+            let moves = opponent.stats.wheel.level.max(1) as usize;
+            for (index, Danger { reach, .. }) in layer.iter_mut().enumerate() {
+                let turns = game::Position::from_linear(index, width)
+                    .manhattan_distance(&opponent.position)
+                    .div_ceil(moves);
+
+                *reach = Some(reach.map_or(turns, |reach| reach.min(turns)));
+            }
+
+            let range = rules::level_get(&rules::RIFLE_RANGES, opponent.stats.rifle.level);
+            for direction in direction::DIRECTIONS {
+                let mut position = opponent.position;
 
-        let top = (0..width).flat_map(|x| (0..level).map(move |y| (x, y)));
-        let bottom = (0..width).flat_map(|x| (height - level..height).map(move |y| (x, y)));
-        let left_middle = (0..level).flat_map(|x| (level..height - level).map(move |y| (x, y)));
-        let right_middle =
-            (width - level..width).flat_map(|x| (level..height - level).map(move |y| (x, y)));
-
-        top.chain(bottom)
-            .chain(left_middle)
-            .chain(right_middle)
-            .map(|(x, y)| game::Position { x, y })
-            .for_each(|position| {
-                let entry = unsafe { self.entry_at_unchecked_mut(position) };
-
-                entry.tile = game::Tile::Acid;
-            });
+                for _ in 0..range {
+                    position = position + direction;
+
+                    let Some(tile) = self.tile_at(position) else {
+                        break;
+                    };
+                    if !matches!(
+                        tile,
+                        game::Tile::Air
+                            | game::Tile::Base
+                            | game::Tile::Acid
+                            | game::Tile::Fog
+                            | game::Tile::Player { .. }
+                    ) {
+                        break;
+                    }
+
+                    layer[position.to_linear(width)].fire += 1;
+                    if matches!(tile, game::Tile::Player { .. }) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.danger = layer.into();
     }
 
     pub(crate) fn find_path(
@@ -185,81 +326,119 @@ impl Map
         wheel_level: u8,
     ) -> Option<Path>
     {
-        let mut location = to;
-        let mut moves = collections::VecDeque::new();
-        let mut mine_direction = None;
+        trace_path(from, to, wheel_level, |location| {
+            self.entry_at(location)?.parent_data
+        })
+    }
 
-        while location != from {
-            let entry = self.entry_at(location)?;
+    // NOTE: Whole turns from `from` until arrival at every tile,
+    // whichever cost model the flood-fill goes by
+    pub(super) fn turns_from(
+        &self,
+        from: game::Position,
+        traveller: Traveller,
+    ) -> Box<[Option<usize>]>
+    {
+        self.a_star(from, None, traveller)
+            .unwrap_or_default()
+            .iter()
+            .map(|searched| {
+                searched.map(|(_, parent_data)| parent_data.map_or(0, |parent| parent.turns))
+            })
+            .collect()
+    }
 
-            let ParentData {
-                direction_from_parent,
-                parent_location,
-                requires_mining,
+    // NOTE: Searched by the turns model, without a destination every
+    // reachable tile gets searched. Indexed like `entries`
+    fn a_star(
+        &self,
+        from: game::Position,
+        to: Option<game::Position>,
+        traveller: Traveller,
+    ) -> Option<Searched>
+    {
+        if !from.is_within_bounds(self.dimensions)
+            || to.is_some_and(|to| !to.is_within_bounds(self.dimensions))
+        {
+            return None;
+        }
 
-                turn_move_index,
-            } = entry.parent_data?;
+        let width = self.dimensions.width;
+        let heuristic = |position: game::Position| {
+            to.map_or(0, |to| position.manhattan_distance(&to) * MIN_STEP)
+        };
 
-            if let Some(ParentData {
-                direction_from_parent,
-                requires_mining,
+        let mut searched = vec![None::<(usize, Option<ParentData>)>; self.entries.len()];
+        let mut closed = vec![false; self.entries.len()];
+        // NOTE: Ties are broken by linear index, like the flood-fill
+        let mut queue = collections::BinaryHeap::new();
 
-                turn_move_index,
-                ..
-            }) = moves.front().copied()
-            {
-                if turn_move_index == 0 {
-                    moves.clear();
+        searched[from.to_linear(width)] = Some((0, None));
+        queue.push(cmp::Reverse((heuristic(from), from.to_linear(width))));
 
-                    mine_direction = if requires_mining {
-                        Some(direction_from_parent)
-                    } else {
-                        None
-                    };
-                }
+        while let Some(cmp::Reverse((_, nearest_index))) = queue.pop() {
+            if ::core::mem::replace(&mut closed[nearest_index], true) {
+                continue;
             }
 
-            moves.push_front(ParentData {
-                direction_from_parent,
-                parent_location: location,
-                requires_mining,
+            let nearest_position = game::Position::from_linear(nearest_index, width);
+            if Some(nearest_position) == to {
+                break;
+            }
 
-                turn_move_index,
-            });
+            // NOTE: Everything queued has been searched first
+            let Some((distance, parent_data)) = searched[nearest_index] else {
+                continue;
+            };
 
-            location = parent_location;
+            for Neighbour {
+                direction,
+                position,
+            } in self.neighbours(nearest_position)
+            {
+                if !position.is_within_bounds(self.dimensions) {
+                    continue;
+                }
 
-            if moves.len() > wheel_level as _ {
-                let _last = moves.pop_back();
-            }
-        }
+                let index = position.to_linear(width);
+                if closed[index] {
+                    continue;
+                }
 
-        let end_position = moves.front().map_or(
-            to,
-            |&ParentData {
-                 parent_location, ..
-             }| parent_location,
-        );
+                let Ok(viable_tile) = game::ViableTile::try_from(self.entries[index].tile) else {
+                    continue;
+                };
 
-        let moves = moves::Moves {
-            mvs: [moves.pop_front(), moves.pop_front(), moves.pop_front()].map(|mv| {
-                if let Some(ParentData {
-                    direction_from_parent,
-                    ..
-                }) = mv
+                let (alternative, turn_move_index, turns) = CostModel::Turns.step(
+                    distance,
+                    parent_data,
+                    viable_tile,
+                    traveller,
+                    self.acid_from[index],
+                    self.danger[index],
+                );
+
+                if searched[index]
+                    .is_none_or(|(searched_distance, _)| alternative < searched_distance)
                 {
-                    Some(direction_from_parent)
-                } else {
-                    None
+                    searched[index] = Some((
+                        alternative,
+                        Some(ParentData {
+                            direction_from_parent: direction,
+                            parent_location: nearest_position,
+                            requires_mining: viable_tile.requires_mining(),
+
+                            turn_move_index,
+                            turns,
+                        }),
+                    ));
+
+                    queue.push(cmp::Reverse((alternative + heuristic(position), index)));
                 }
-            }),
-        };
+            }
+        }
 
-        Some(Path {
-            moves,
-            end_position,
-            mine_direction,
-        })
+        Some(searched.into())
     }
 
     fn entry_at(&self, position: game::Position) -> Option<&Entry>
@@ -267,6 +446,23 @@ impl Map
         self.entries.get(position.to_linear(self.dimensions.width))
     }
 
+    pub(super) fn tile_at(&self, position: game::Position) -> Option<game::Tile>
+    {
+        if !position.is_within_bounds(self.dimensions) {
+            return None;
+        }
+
+        self.entry_at(position).map(|Entry { tile, .. }| *tile)
+    }
+
+    // NOTE: Going by the rules rather than by what the map
+    // shows, which can be a player standing in the acid
+    pub(super) fn is_acid(&self, position: game::Position, turn: usize) -> bool
+    {
+        position.is_within_bounds(self.dimensions)
+            && self.acid_from[position.to_linear(self.dimensions.width)] <= turn
+    }
+
     pub(super) fn tile_at_is<P>(&self, position: game::Position, pattern: P) -> bool
     where
         P: tile::Pattern,
@@ -290,8 +486,72 @@ impl Map
 
     pub(super) fn distance_to(&self, position: game::Position) -> Option<usize>
     {
-        self.entry_at(position)
-            .map(|Entry { distance, .. }| *distance)
+        self.entry_at(position).map(
+            |Entry {
+                 distance,
+                 parent_data,
+                 ..
+             }| match self.cost_model {
+                CostModel::Weighted => *distance,
+                CostModel::Turns => parent_data.map_or(*distance, |parent| parent.turns),
+            },
+        )
+    }
+
+    // NOTE: Whole turns until arrival at every tile,
+    // the first turn's moves arriving after one
+    pub(super) fn turns(&self) -> Box<[Option<usize>]>
+    {
+        self.entries
+            .iter()
+            .map(
+                |Entry {
+                     distance,
+                     parent_data,
+                     ..
+                 }| {
+                    (*distance < Entry::default().distance)
+                        .then(|| parent_data.map_or(0, |parent| parent.turns))
+                },
+            )
+            .collect()
+    }
+
+    // NOTE: Turns to spare along the quickest way from `from` to `to`
+    // before any tile on it turns to acid under the player, negative
+    // once one already has. Counted in whole turns whichever cost
+    // model the flood-fill goes by, which would overestimate it
+    pub(super) fn acid_slack(
+        &self,
+        from: game::Position,
+        to: game::Position,
+        traveller: Traveller,
+    ) -> Option<isize>
+    {
+        let searched = self.a_star(from, Some(to), traveller)?;
+
+        let mut location = to;
+        let mut slack = isize::MAX;
+
+        loop {
+            let index = location.to_linear(self.dimensions.width);
+            let (_, parent_data) = searched[index].as_ref()?;
+
+            // NOTE: Moves made during the first turn
+            // arrive on the current one
+            let arrival = traveller.turn
+                + parent_data
+                    .map_or(0, |parent| parent.turns)
+                    .saturating_sub(1);
+            slack = slack.min(self.acid_from[index] as isize - arrival as isize);
+
+            match parent_data {
+                Some(ParentData {
+                    parent_location, ..
+                }) => location = *parent_location,
+                None => return Some(slack),
+            }
+        }
     }
 
     pub(super) fn center(&self) -> game::Position
@@ -302,12 +562,33 @@ impl Map
         }
     }
 
-    pub(super) fn nearest_tile(&self, np_tile: game::NonPlayerTile) -> Option<game::Position>
+    // NOTE: With `seen_since`, tiles last seen before
+    // that turn may well be gone and are left out
+    pub(super) fn nearest_tile(
+        &self,
+        np_tile: game::NonPlayerTile,
+        seen_since: Option<usize>,
+    ) -> Option<game::Position>
     {
-        self.find_tiles(np_tile)
+        self.seen_tiles(np_tile, seen_since)
             .min_by_key(|&position| self.distance_to(position))
     }
 
+    pub(super) fn seen_tiles(
+        &self,
+        np_tile: game::NonPlayerTile,
+        seen_since: Option<usize>,
+    ) -> impl Iterator<Item = game::Position> + '_
+    {
+        self.find_tiles(np_tile).filter(move |&position| {
+            seen_since.is_none_or(|seen_since| {
+                self.entry_at(position)
+                    .and_then(|Entry { last_seen, .. }| *last_seen)
+                    .is_some_and(|last_seen| last_seen >= seen_since)
+            })
+        })
+    }
+
     fn find_tiles(&self, np_tile: game::NonPlayerTile) -> FindTiles<'_>
     {
         FindTiles {
@@ -344,11 +625,126 @@ impl Map
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) enum CostModel
+{
+    // NOTE: Hand-tuned tile weights, favouring ores along the way
+    #[default]
+    Weighted,
+    // NOTE: Game turns until arrival, `distance_to` gives whole turns
+    Turns,
+}
+
+// NOTE: The cheapest step under the turns model, a single move
+// within a turn, keeping the search's heuristic admissible
+const MIN_STEP: usize = 1;
+
+// NOTE: Turns-model distances pack the turn of arrival
+// with the moves already spent during that turn
+const TURN_STRIDE: usize = 4;
+
+impl CostModel
+{
+    // NOTE: The distance after stepping onto `viable_tile`, the index
+    // of that move within its turn and the turns taken to get there
+    fn step(
+        self,
+        distance: usize,
+        parent_data: Option<ParentData>,
+        viable_tile: game::ViableTile,
+        traveller: Traveller,
+        acid_from: usize,
+        tile_danger: Danger,
+    ) -> (usize, usize, usize)
+    {
+        let requires_mining = viable_tile.requires_mining();
+        let parent_turns = parent_data.map_or(0, |parent| parent.turns);
+
+        let (alternative, turn_move_index, turns) = match self {
+            CostModel::Weighted => {
+                let first_move = parent_data.is_none();
+                let turn_move_index = parent_data.map_or(0, |parent| {
+                    (parent.turn_move_index + 1) % (traveller.wheel_level as usize)
+                });
+
+                let alternative = distance
+                    + if requires_mining && first_move {
+                        1000
+                    } else if requires_mining && turn_move_index == 0 {
+                        1
+                    } else {
+                        viable_tile.weight()
+                    };
+                let turn_move_index = if requires_mining { 0 } else { turn_move_index };
+
+                (
+                    alternative,
+                    turn_move_index,
+                    parent_turns + (turn_move_index == 0) as usize,
+                )
+            }
+            CostModel::Turns => {
+                // NOTE: Standing on the source, the first turn
+                // is about to start with no moves spent yet
+                let (parent_turn, parent_moves) = match parent_data {
+                    None => (1, 0),
+                    Some(parent) => (parent.turns, parent.turn_move_index + 1),
+                };
+
+                // NOTE: Mining is the turn's action, so it ends the turn
+                // and the freed tile can only be entered on the next one
+                let (turn, moves) = if requires_mining {
+                    let hits = match viable_tile {
+                        game::ViableTile::Stone | game::ViableTile::Cobblestone => {
+                            rules::level_get(&rules::STONE_HITS, traveller.drill_level)
+                        }
+                        _ => 1,
+                    };
+
+                    (parent_turn + hits as usize, 1)
+                } else if parent_moves < traveller.wheel_level as usize {
+                    (parent_turn, parent_moves + 1)
+                } else {
+                    (parent_turn + 1, 1)
+                };
+
+                (
+                    distance + (turn * TURN_STRIDE + moves)
+                        - (parent_turn * TURN_STRIDE + parent_moves),
+                    moves - 1,
+                    turn,
+                )
+            }
+        };
+
+        // NOTE: Arriving after the tile turned to acid is only ever worth
+        // it when there's no other way, such as when already standing in it
+        let arrival = traveller.turn + turns.saturating_sub(1);
+        let alternative = if arrival >= acid_from {
+            alternative + acid::ARRIVAL_PENALTY
+        } else {
+            alternative
+        };
+
+        // NOTE: Firing lanes are crossed when there's no way around them,
+        // tiles opponents get to first only when there's no faster way
+        let contested = tile_danger
+            .reach
+            .is_some_and(|reach| reach <= turns.min(danger::HORIZON));
+        let alternative = alternative
+            + tile_danger.fire as usize * danger::FIRE_PENALTY
+            + contested as usize * danger::CONTESTED_PENALTY;
+
+        (alternative, turn_move_index, turns)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(super) struct Entry
 {
     pub(super) tile: game::Tile,
     pub(super) distance: usize,
+    pub(super) last_seen: Option<usize>,
 
     parent_data: Option<ParentData>,
 }
@@ -371,6 +767,7 @@ impl Default for Entry
         Entry {
             tile: game::Tile::Fog,
             distance: usize::MAX / 2,
+            last_seen: None,
 
             parent_data: None,
         }
@@ -385,6 +782,96 @@ struct ParentData
     requires_mining: bool,
 
     turn_move_index: usize,
+    turns: usize,
+}
+
+// NOTE: The distance and parent of every tile a search reached
+type Searched = Box<[Option<(usize, Option<ParentData>)>]>;
+
+fn trace_path<Parent>(
+    from: game::Position,
+    to: game::Position,
+    wheel_level: u8,
+    parent_of: Parent,
+) -> Option<Path>
+where
+    Parent: Fn(game::Position) -> Option<ParentData>,
+{
+    let mut location = to;
+    let mut moves = collections::VecDeque::new();
+    let mut mine_direction = None;
+
+    while location != from {
+        let ParentData {
+            direction_from_parent,
+            parent_location,
+            requires_mining,
+
+            turn_move_index,
+            turns,
+        } = parent_of(location)?;
+
+        if let Some(ParentData {
+            direction_from_parent,
+            requires_mining,
+
+            turn_move_index,
+            ..
+        }) = moves.front().copied()
+        {
+            if turn_move_index == 0 {
+                moves.clear();
+
+                mine_direction = if requires_mining {
+                    Some(direction_from_parent)
+                } else {
+                    None
+                };
+            }
+        }
+
+        moves.push_front(ParentData {
+            direction_from_parent,
+            parent_location: location,
+            requires_mining,
+
+            turn_move_index,
+            turns,
+        });
+
+        location = parent_location;
+
+        if moves.len() > wheel_level as _ {
+            let _last = moves.pop_back();
+        }
+    }
+
+    let end_position = moves.front().map_or(
+        to,
+        |&ParentData {
+             parent_location, ..
+         }| parent_location,
+    );
+
+    let moves = moves::Moves {
+        mvs: [moves.pop_front(), moves.pop_front(), moves.pop_front()].map(|mv| {
+            if let Some(ParentData {
+                direction_from_parent,
+                ..
+            }) = mv
+            {
+                Some(direction_from_parent)
+            } else {
+                None
+            }
+        }),
+    };
+
+    Some(Path {
+        moves,
+        end_position,
+        mine_direction,
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -411,7 +898,7 @@ impl<'entries> Iterator for FindTiles<'entries>
 
     fn next(&mut self) -> Option<Self::Item>
     {
-        while let Some(Entry { tile, .. }) = self.entries.take_first() {
+        while let Some(Entry { tile, .. }) = self.entries.split_off_first() {
             if *tile == self.np_tile {
                 let position = game::Position::from_linear(self.index, self.width);
 
@@ -435,3 +922,6 @@ pub(super) struct Neighbour
     pub(super) direction: game::Direction,
     pub(super) position: game::Position,
 }
+
+#[cfg(test)]
+mod tests;
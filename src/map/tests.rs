@@ -0,0 +1,478 @@
+use super::*;
+
+// NOTE: Deterministic maps without pulling in a random number crate
+struct Lcg(u64);
+
+impl Lcg
+{
+    fn next(&mut self, bound: usize) -> usize
+    {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+
+        (self.0 >> 33) as usize % bound
+    }
+}
+
+const TILES: [game::Tile; 10] = [
+    game::Tile::Air,
+    game::Tile::Air,
+    game::Tile::Air,
+    game::Tile::Stone,
+    game::Tile::Stone,
+    game::Tile::Iron,
+    game::Tile::Osmium,
+    game::Tile::Bedrock,
+    game::Tile::Acid,
+    game::Tile::Fog,
+];
+
+fn random_map(lcg: &mut Lcg, size: usize, cost_model: CostModel) -> Map
+{
+    let entries = (0..size * size)
+        .map(|_| Entry::init(TILES[lcg.next(TILES.len())]))
+        .collect();
+
+    Map::new(
+        Dimensions {
+            width: size,
+            height: size,
+        },
+        entries,
+        cost_model,
+    )
+}
+
+fn random_traveller(lcg: &mut Lcg) -> Traveller
+{
+    Traveller {
+        wheel_level: lcg.next(3) as u8 + 1,
+        drill_level: lcg.next(3) as u8 + 1,
+        // NOTE: Around the acid start, so that some arrivals get penalised
+        turn: acid::START_TURN - 20 + lcg.next(40),
+    }
+}
+
+fn random_position(lcg: &mut Lcg, size: usize) -> game::Position
+{
+    game::Position {
+        x: lcg.next(size) as _,
+        y: lcg.next(size) as _,
+    }
+}
+
+// NOTE: The flood-fill as it was before the heap, scanning every
+// unsettled entry for the nearest one, ties going to the lower index
+fn linear_flood_fill(map: &mut Map, player: game::Position, traveller: Traveller)
+{
+    let width = map.dimensions.width;
+
+    for entry in map.entries.iter_mut() {
+        *entry = Entry {
+            tile: entry.tile,
+            last_seen: entry.last_seen,
+            ..Default::default()
+        };
+    }
+    map.entries[player.to_linear(width)].distance = 0;
+
+    let mut unsettled = vec![true; map.entries.len()];
+    while let Some(nearest_index) = (0..map.entries.len())
+        .filter(|&index| unsettled[index])
+        .min_by_key(|&index| (map.entries[index].distance, index))
+    {
+        unsettled[nearest_index] = false;
+
+        let nearest_position = game::Position::from_linear(nearest_index, width);
+        let Entry {
+            parent_data,
+            distance,
+            ..
+        } = map.entries[nearest_index].clone();
+
+        for Neighbour {
+            direction,
+            position,
+        } in map.neighbours(nearest_position)
+        {
+            if !position.is_within_bounds(map.dimensions) {
+                continue;
+            }
+
+            let index = position.to_linear(width);
+            if !unsettled[index] {
+                continue;
+            }
+
+            let Ok(viable_tile) = game::ViableTile::try_from(map.entries[index].tile) else {
+                continue;
+            };
+
+            let (alternative, turn_move_index, turns) = map.cost_model.step(
+                distance,
+                parent_data,
+                viable_tile,
+                traveller,
+                map.acid_from[index],
+                map.danger[index],
+            );
+
+            if alternative < map.entries[index].distance {
+                map.entries[index] = Entry {
+                    distance: alternative,
+                    parent_data: Some(ParentData {
+                        direction_from_parent: direction,
+                        parent_location: nearest_position,
+                        requires_mining: viable_tile.requires_mining(),
+
+                        turn_move_index,
+                        turns,
+                    }),
+
+                    ..map.entries[index].clone()
+                };
+            }
+        }
+    }
+}
+
+fn random_danger(lcg: &mut Lcg, range: usize) -> Danger
+{
+    Danger {
+        fire: lcg.next(2) as u8,
+        reach: (lcg.next(2) == 0).then(|| lcg.next(range)),
+    }
+}
+
+#[test]
+fn flood_fill_matches_linear_scan()
+{
+    let mut lcg = Lcg(7);
+
+    for cost_model in [CostModel::Weighted, CostModel::Turns] {
+        for size in [1, 2, 5, 10, 25] {
+            for _ in 0..20 {
+                let mut map = random_map(&mut lcg, size, cost_model);
+                map.danger = (0..size * size)
+                    .map(|_| random_danger(&mut lcg, 5))
+                    .collect();
+                let player = random_position(&mut lcg, size);
+                let traveller = random_traveller(&mut lcg);
+
+                let mut expected = map.clone();
+                linear_flood_fill(&mut expected, player, traveller);
+                map.flood_fill(player, traveller);
+
+                assert_eq!(map.entries, expected.entries);
+            }
+        }
+    }
+}
+
+#[test]
+fn turns_from_matches_turns_model_flood_fill()
+{
+    let mut lcg = Lcg(11);
+
+    for size in [1, 2, 5, 10, 25] {
+        for _ in 0..20 {
+            let mut map = random_map(&mut lcg, size, CostModel::Turns);
+            map.danger = (0..size * size)
+                .map(|_| random_danger(&mut lcg, 5))
+                .collect();
+            let player = random_position(&mut lcg, size);
+            let traveller = random_traveller(&mut lcg);
+
+            map.flood_fill(player, traveller);
+
+            assert_eq!(map.turns_from(player, traveller), map.turns());
+        }
+    }
+}
+
+fn bench_flood_fill<Fill>(bencher: &mut ::test::Bencher, size: usize, fill: Fill)
+where
+    Fill: Fn(&mut Map, game::Position, Traveller),
+{
+    let mut lcg = Lcg(7);
+    let mut map = random_map(&mut lcg, size, CostModel::Weighted);
+    let player = game::Position {
+        x: size as isize / 2,
+        y: size as isize / 2,
+    };
+    let traveller = Traveller {
+        wheel_level: 1,
+        drill_level: 1,
+        turn: 0,
+    };
+
+    bencher.iter(|| {
+        fill(&mut map, player, traveller);
+    });
+}
+
+#[bench]
+fn flood_fill_30x30(bencher: &mut ::test::Bencher)
+{
+    bench_flood_fill(bencher, 30, Map::flood_fill);
+}
+
+#[bench]
+fn flood_fill_60x60(bencher: &mut ::test::Bencher)
+{
+    bench_flood_fill(bencher, 60, Map::flood_fill);
+}
+
+#[bench]
+fn flood_fill_100x100(bencher: &mut ::test::Bencher)
+{
+    bench_flood_fill(bencher, 100, Map::flood_fill);
+}
+
+#[bench]
+fn flood_fill_150x150(bencher: &mut ::test::Bencher)
+{
+    bench_flood_fill(bencher, 150, Map::flood_fill);
+}
+
+#[bench]
+fn linear_flood_fill_30x30(bencher: &mut ::test::Bencher)
+{
+    bench_flood_fill(bencher, 30, linear_flood_fill);
+}
+
+#[bench]
+fn linear_flood_fill_60x60(bencher: &mut ::test::Bencher)
+{
+    bench_flood_fill(bencher, 60, linear_flood_fill);
+}
+
+#[bench]
+fn linear_flood_fill_100x100(bencher: &mut ::test::Bencher)
+{
+    bench_flood_fill(bencher, 100, linear_flood_fill);
+}
+
+#[bench]
+fn linear_flood_fill_150x150(bencher: &mut ::test::Bencher)
+{
+    bench_flood_fill(bencher, 150, linear_flood_fill);
+}
+
+fn input(rows: &[&str], player: (isize, isize)) -> input::Input
+{
+    let (x, y) = player;
+
+    input::try_parse(format!(
+        "{} {}\n{}\n{x} {y}\n10 1 1 1 1 0 0\n0 0 0",
+        rows[0].len(),
+        rows.len(),
+        rows.join("\n")
+    ))
+    .unwrap()
+}
+
+fn updated(map: &mut Map, input: &input::Input, turn: usize)
+{
+    map.update_with(input, &opponents::Opponents::default(), turn);
+}
+
+#[test]
+fn update_with_keeps_fogged_tiles_as_last_seen()
+{
+    let seen = input(&["....C", ".....", ".....", ".....", "C...."], (2, 2));
+    let mut map = Map::new(
+        seen.dimensions,
+        seen.map.tiles.iter().copied().map(Entry::init).collect(),
+        CostModel::Weighted,
+    );
+    updated(&mut map, &seen, 3);
+
+    let fogged = input(&["...??", "...??", ".....", ".....", "....."], (2, 2));
+    updated(&mut map, &fogged, 9);
+
+    let corner = |x, y| map.entries[game::Position { x, y }.to_linear(5)].clone();
+    assert_eq!(corner(4, 0).tile, game::Tile::Iron);
+    assert_eq!(corner(4, 0).last_seen, Some(3));
+    // NOTE: Mined out while in sight
+    assert_eq!(corner(0, 4).tile, game::Tile::Air);
+    assert_eq!(corner(0, 4).last_seen, Some(9));
+}
+
+#[test]
+fn nearest_tile_leaves_out_tiles_seen_too_long_ago()
+{
+    let seen = input(&["C...D", ".....", ".....", ".....", "D...C"], (1, 0));
+    let mut map = Map::new(
+        seen.dimensions,
+        seen.map.tiles.iter().copied().map(Entry::init).collect(),
+        CostModel::Weighted,
+    );
+    updated(&mut map, &seen, 3);
+
+    let fogged = input(&["??..D", "??...", ".....", ".....", "....C"], (1, 0));
+    updated(&mut map, &fogged, 9);
+
+    let stale = game::Position { x: 0, y: 0 };
+    let fresh = game::Position { x: 4, y: 4 };
+    assert_eq!(
+        map.nearest_tile(game::NonPlayerTile::Iron, None),
+        Some(stale)
+    );
+    assert_eq!(
+        map.nearest_tile(game::NonPlayerTile::Iron, Some(3)),
+        Some(stale)
+    );
+    assert_eq!(
+        map.nearest_tile(game::NonPlayerTile::Iron, Some(4)),
+        Some(fresh)
+    );
+    assert_eq!(
+        map.nearest_tile(game::NonPlayerTile::Osmium, Some(10)),
+        None
+    );
+}
+
+fn open_map(width: usize, height: usize, cost_model: CostModel) -> Map
+{
+    Map::new(
+        Dimensions { width, height },
+        (0..width * height)
+            .map(|_| Entry::init(game::Tile::Air))
+            .collect(),
+        cost_model,
+    )
+}
+
+#[test]
+fn acid_closes_in_a_ring_per_tick()
+{
+    let map = open_map(7, 5, CostModel::Weighted);
+    let acid_from = |x, y| map.acid_from[game::Position { x, y }.to_linear(7)];
+
+    assert_eq!(acid_from(0, 2), acid::START_TURN);
+    assert_eq!(acid_from(6, 4), acid::START_TURN);
+    assert_eq!(acid_from(1, 2), acid::START_TURN + acid::TICK_RATE);
+    assert_eq!(acid_from(3, 2), acid::START_TURN + 2 * acid::TICK_RATE);
+}
+
+#[test]
+fn flood_fill_penalises_arriving_after_the_acid()
+{
+    let mut map = open_map(7, 7, CostModel::Weighted);
+    let player = game::Position { x: 1, y: 3 };
+    let border = game::Position { x: 0, y: 3 };
+    let distance_at = |map: &mut Map, turn| {
+        map.flood_fill(
+            player,
+            Traveller {
+                wheel_level: 1,
+                drill_level: 1,
+                turn,
+            },
+        );
+
+        map.entries[border.to_linear(7)].distance
+    };
+
+    let before = distance_at(&mut map, acid::START_TURN - 1);
+    let after = distance_at(&mut map, acid::START_TURN);
+    assert_eq!(after - before, acid::ARRIVAL_PENALTY);
+}
+
+// NOTE: Heading out from the middle, the border is
+// the last tile reached and the first to go
+#[test]
+fn acid_slack_counts_the_turns_to_spare()
+{
+    let map = open_map(9, 9, CostModel::Weighted);
+    let from = game::Position { x: 4, y: 4 };
+    let to = game::Position { x: 0, y: 4 };
+    let slack = |wheel_level, turn| {
+        map.acid_slack(
+            from,
+            to,
+            Traveller {
+                wheel_level,
+                drill_level: 1,
+                turn,
+            },
+        )
+    };
+
+    assert_eq!(slack(1, acid::START_TURN - 5), Some(2));
+    assert_eq!(slack(1, acid::START_TURN), Some(-3));
+    assert_eq!(slack(2, acid::START_TURN - 5), Some(4));
+    assert_eq!(
+        map.acid_slack(from, game::Position { x: 9, y: 4 }, Traveller::default()),
+        None
+    );
+}
+
+fn danger_scene(rows: &[&str]) -> (Map, opponents::Opponents)
+{
+    let input = input(rows, (0, 0));
+    let mut opponents = opponents::Opponents::default();
+    opponents.update_with(&input.map.tiles, input.dimensions.width, 1);
+
+    let mut map = Map::new(
+        input.dimensions,
+        input.map.tiles.iter().copied().map(Entry::init).collect(),
+        CostModel::Weighted,
+    );
+    map.update_danger(&opponents);
+
+    (map, opponents)
+}
+
+#[test]
+fn update_danger_marks_firing_lanes_and_reach()
+{
+    let (mut map, mut opponents) =
+        danger_scene(&[".......", ".......", "..1.X..", ".......", "......."]);
+    let danger = |map: &Map, x, y| map.danger[game::Position { x, y }.to_linear(7)];
+
+    // NOTE: At the first rifle level, two tiles each way
+    // and no further than the stone in the way
+    for (x, y) in [(0, 2), (1, 2), (3, 2), (2, 0), (2, 1), (2, 3), (2, 4)] {
+        assert_eq!(danger(&map, x, y).fire, 1, "{x} {y}");
+    }
+    for (x, y) in [(4, 2), (5, 2), (1, 1), (2, 2)] {
+        assert_eq!(danger(&map, x, y).fire, 0, "{x} {y}");
+    }
+    assert_eq!(danger(&map, 6, 4).reach, Some(6));
+
+    let opponent = opponents.opponents.get_mut(&1).unwrap();
+    opponent
+        .stats
+        .wheel
+        .observe(2, opponents::Confidence::Certain);
+    map.update_danger(&opponents);
+    assert_eq!(danger(&map, 6, 4).reach, Some(3));
+
+    opponents.outdate_opponents();
+    map.update_danger(&opponents);
+    assert_eq!(danger(&map, 3, 2), Danger::default());
+}
+
+#[test]
+fn flood_fill_charges_for_crossing_lanes_and_contested_tiles()
+{
+    let (mut map, _) = danger_scene(&[".......", ".......", "..1....", ".......", "......."]);
+    let traveller = Traveller {
+        wheel_level: 1,
+        drill_level: 1,
+        turn: 1,
+    };
+    let distance = |map: &Map, x, y| map.entries[game::Position { x, y }.to_linear(7)].distance;
+
+    map.flood_fill(game::Position { x: 3, y: 3 }, traveller);
+    assert_eq!(
+        distance(&map, 3, 2),
+        game::ViableTile::Air.weight() + danger::FIRE_PENALTY + danger::CONTESTED_PENALTY
+    );
+    // NOTE: Out of reach within the horizon
+    assert_eq!(distance(&map, 6, 3), 3 * game::ViableTile::Air.weight());
+}
@@ -1,6 +1,10 @@
 use std::collections;
 
-use crate::game;
+use crate::{
+    constants::rules,
+    game::{self, direction},
+    map,
+};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(super) struct Opponents
@@ -18,17 +22,19 @@ pub(crate) struct Opponent
     pub(super) stats: Stats,
 
     pub(super) up_to_date: bool,
+    pub(super) last_seen: usize,
 }
 
 impl Opponent
 {
-    fn init_with_position(id: Id, position: game::Position) -> Self
+    fn init_with_position(id: Id, position: game::Position, turn: usize) -> Self
     {
         Opponent {
             id,
             position,
 
             up_to_date: true,
+            last_seen: turn,
             ..Default::default()
         }
     }
@@ -38,30 +44,38 @@ impl Opponent
         self.up_to_date = false;
     }
 
-    fn update(&mut self, position: game::Position)
+    // NOTE: No turn takes anyone farther than their wheel level, so
+    // whatever distance they covered bounds it from below. Over a
+    // single turn that bound is as tight as it gets, after a while
+    // out of sight the average per turn bounds it just as surely,
+    // only falling further short
+    fn update(&mut self, position: game::Position, turn: usize)
     {
-        let wheel_level = if self.up_to_date {
-            self.position.manhattan_distance(&position)
-        } else {
-            1
-        } as _;
+        let elapsed = turn.saturating_sub(self.last_seen);
+
+        if elapsed > 0 {
+            let level = self
+                .position
+                .manhattan_distance(&position)
+                .div_ceil(elapsed)
+                .min(rules::MAX_WHEEL_LEVEL as _);
+
+            self.stats.wheel.observe(level as _, Confidence::Certain);
+        }
 
         *self = Opponent {
-            id: self.id,
             position,
-            stats: Stats {
-                rifle_level: self.stats.rifle_level,
-                wheel_level,
-            },
 
             up_to_date: true,
+            last_seen: turn,
+            ..*self
         };
     }
 }
 
 impl Opponents
 {
-    pub(super) fn update_with(&mut self, tiles: &[game::Tile], stride: usize)
+    pub(super) fn update_with(&mut self, tiles: &[game::Tile], stride: usize, turn: usize)
     {
         self.outdate_opponents();
 
@@ -80,8 +94,8 @@ impl Opponents
                 let _opponent = self
                     .opponents
                     .entry(id)
-                    .and_modify(|opponent| opponent.update(position))
-                    .or_insert(Opponent::init_with_position(id, position));
+                    .and_modify(|opponent| opponent.update(position, turn))
+                    .or_insert(Opponent::init_with_position(id, position, turn));
             });
     }
 
@@ -89,22 +103,177 @@ impl Opponents
     {
         self.opponents.values_mut().for_each(Opponent::outdate);
     }
+
+    // NOTE: A turn's damage can add up from several shots, so it's only
+    // put down to a single rifle when no two shooters could make it up
+    // together, and only pinned on an opponent when it's the one robot
+    // that could have fired, with every lane to `target` in sight
+    pub(super) fn attribute_hit(
+        &mut self,
+        map: &map::Map,
+        target: game::Position,
+        damage: u8,
+        turn: usize,
+    )
+    {
+        let (in_line, lanes_seen) = lanes(map, target, turn);
+
+        // NOTE: Out of sight, any number of shooters could hide
+        let shooter_bound = if lanes_seen {
+            in_line.len()
+        } else {
+            damage as usize
+        };
+        if adds_up_from_several(damage, shooter_bound) {
+            return;
+        }
+
+        let Some(index) = rules::RIFLE_DAMAGES
+            .iter()
+            .position(|&rifle_damage| rifle_damage == damage)
+        else {
+            return;
+        };
+        let rifle_level = index as u8 + 1;
+        let range = rules::level_get(&rules::RIFLE_RANGES, rifle_level);
+
+        let shooters = in_line
+            .iter()
+            .filter(|(_, position)| target.manhattan_distance(position) <= range)
+            .filter(|(id, position)| {
+                self.opponents
+                    .get(id)
+                    .is_some_and(|opponent| opponent.up_to_date && opponent.position == *position)
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        let confidence = match shooters.len() {
+            1 if lanes_seen && in_line.len() == 1 => Confidence::Certain,
+            _ => Confidence::Possible,
+        };
+        for id in shooters {
+            if let Some(opponent) = self.opponents.get_mut(&id) {
+                opponent.stats.rifle.observe(rifle_level, confidence);
+            }
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+// NOTE: The robots in line with `target` within the longest rifle range
+// with nothing in between, and whether every tile a shot could have
+// come through was seen on `turn`, so that no one else could be there
+fn lanes(map: &map::Map, target: game::Position, turn: usize) -> (Vec<(Id, game::Position)>, bool)
+{
+    let range = rules::RIFLE_RANGES
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or_default();
+    let mut in_line = Vec::new();
+    let mut seen = true;
+
+    for direction in direction::DIRECTIONS {
+        let mut position = target;
+
+        for _ in 0..range {
+            position = position + direction;
+
+            let Some(map::Entry {
+                tile, last_seen, ..
+            }) = position
+                .is_within_bounds(map.dimensions)
+                .then(|| &map.entries[position.to_linear(map.dimensions.width)])
+            else {
+                break;
+            };
+            if *last_seen != Some(turn) {
+                seen = false;
+                break;
+            }
+
+            match tile {
+                game::Tile::Player { id } => {
+                    in_line.push((*id, position));
+                    break;
+                }
+                game::Tile::Air | game::Tile::Base | game::Tile::Acid => {}
+                _ => break,
+            }
+        }
+    }
+
+    (in_line, seen)
+}
+
+// NOTE: Whether two to `shooters` shots, a single one each,
+// could have dealt `damage` between them
+fn adds_up_from_several(damage: u8, shooters: usize) -> bool
+{
+    // NOTE: Bit `n` is set when `n` damage can be dealt
+    let mut sums = 1_u64;
+
+    (1..=shooters.min(rules::MAX_HIT_POINTS as usize)).any(|shots| {
+        sums = rules::RIFLE_DAMAGES
+            .iter()
+            .fold(0, |next, &rifle_damage| next | sums << rifle_damage);
+
+        shots >= 2 && sums >> damage & 1 == 1
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub(super) struct Stats
 {
-    pub(super) rifle_level: u8,
-    pub(super) wheel_level: u8,
+    pub(super) rifle: Estimate,
+    pub(super) wheel: Estimate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct Estimate
+{
+    pub(super) level: u8,
+    pub(super) confidence: Confidence,
 }
 
-impl Default for Stats
+// NOTE: How sure it is that the opponent reached at least the
+// estimated level, estimates never telling more than that
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(super) enum Confidence
+{
+    // NOTE: Nothing seen yet, everyone starts at the first level
+    #[default]
+    Assumed,
+    // NOTE: Seen, but it might have been someone else, such as
+    // a hit that more than one opponent could have fired
+    Possible,
+    Certain,
+}
+
+impl Estimate
+{
+    // NOTE: Levels only ever go up, so a higher one always stands
+    // while the same one again can only firm up what's known
+    pub(super) fn observe(&mut self, level: u8, confidence: Confidence)
+    {
+        if level > self.level {
+            *self = Estimate { level, confidence };
+        } else if level == self.level {
+            self.confidence = self.confidence.max(confidence);
+        }
+    }
+}
+
+impl Default for Estimate
 {
     fn default() -> Self
     {
-        Stats {
-            rifle_level: 1,
-            wheel_level: 1,
+        Estimate {
+            level: 1,
+            confidence: Confidence::Assumed,
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
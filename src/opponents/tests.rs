@@ -0,0 +1,226 @@
+use super::*;
+use crate::io::input::map::tile;
+
+const TURN: usize = 10;
+
+// NOTE: `?` is fog, every other tile was seen on `TURN`, and digits
+// are opponents at the first levels, seen right there
+fn scene(rows: &[&str]) -> (map::Map, Opponents)
+{
+    let dimensions = game::Dimensions {
+        width: rows[0].len(),
+        height: rows.len(),
+    };
+    let tiles = rows
+        .iter()
+        .flat_map(|row| row.bytes())
+        .map(|byte| tile::try_parse(byte).unwrap())
+        .collect::<Vec<_>>();
+
+    let entries = tiles
+        .iter()
+        .map(|&tile| {
+            let mut entry = map::Entry::init(tile);
+            entry.last_seen = (tile != game::Tile::Fog).then_some(TURN);
+
+            entry
+        })
+        .collect();
+
+    let mut opponents = Opponents::default();
+    opponents.update_with(&tiles, dimensions.width, TURN);
+
+    (
+        map::Map::new(dimensions, entries, map::CostModel::Weighted),
+        opponents,
+    )
+}
+
+fn rifle(opponents: &Opponents, id: Id) -> Estimate
+{
+    opponents.opponents[&id].stats.rifle
+}
+
+const TARGET: game::Position = game::Position { x: 3, y: 3 };
+
+#[test]
+fn lone_shooter_in_full_sight_is_certain()
+{
+    let (map, mut opponents) = scene(&[
+        ".......", //
+        ".......", //
+        ".......", //
+        ".....1.", //
+        ".......", //
+        ".......", //
+        ".......", //
+    ]);
+
+    opponents.attribute_hit(&map, TARGET, 2, TURN);
+
+    assert_eq!(
+        rifle(&opponents, 1),
+        Estimate {
+            level: 2,
+            confidence: Confidence::Certain
+        }
+    );
+}
+
+#[test]
+fn simultaneous_hits_raise_no_one()
+{
+    let (map, mut opponents) = scene(&[
+        ".......", //
+        "...2...", //
+        ".......", //
+        ".....1.", //
+        ".......", //
+        ".......", //
+        ".......", //
+    ]);
+
+    // NOTE: Two first level hits, or a second level and a first level one
+    for damage in [2, 3] {
+        opponents.attribute_hit(&map, TARGET, damage, TURN);
+
+        assert_eq!(rifle(&opponents, 1), Estimate::default(), "{damage}");
+        assert_eq!(rifle(&opponents, 2), Estimate::default(), "{damage}");
+    }
+}
+
+#[test]
+fn shooter_hidden_in_fog_could_have_added_to_the_hit()
+{
+    let (map, mut opponents) = scene(&[
+        "...?...", //
+        "...?...", //
+        ".......", //
+        ".....1.", //
+        ".......", //
+        ".......", //
+        ".......", //
+    ]);
+
+    opponents.attribute_hit(&map, TARGET, 2, TURN);
+    assert_eq!(rifle(&opponents, 1), Estimate::default());
+
+    // NOTE: Only a third level rifle deals three in one shot, but
+    // two first level shots and one from fog would add up as well
+    opponents.attribute_hit(&map, TARGET, 3, TURN);
+    assert_eq!(rifle(&opponents, 1), Estimate::default());
+}
+
+#[test]
+fn lanes_stop_at_the_first_obstacle()
+{
+    let (map, mut opponents) = scene(&[
+        "...2...", //
+        "...X...", //
+        ".......", //
+        ".....1.", //
+        ".......", //
+        ".......", //
+        ".......", //
+    ]);
+
+    opponents.attribute_hit(&map, TARGET, 2, TURN);
+
+    assert_eq!(rifle(&opponents, 1).confidence, Confidence::Certain);
+    assert_eq!(rifle(&opponents, 2), Estimate::default());
+}
+
+#[test]
+fn several_shots_are_told_apart_from_one()
+{
+    assert!(!adds_up_from_several(1, 8));
+    assert!(!adds_up_from_several(3, 1));
+    assert!(adds_up_from_several(2, 2));
+    assert!(adds_up_from_several(3, 2));
+    assert!(!adds_up_from_several(7, 2));
+    assert!(adds_up_from_several(7, 3));
+}
+
+fn sight(opponents: &mut Opponents, row: &str, turn: usize)
+{
+    let tiles = row
+        .bytes()
+        .map(|byte| tile::try_parse(byte).unwrap())
+        .collect::<Vec<_>>();
+
+    opponents.update_with(&tiles, tiles.len(), turn);
+}
+
+fn wheel(opponents: &Opponents, id: Id) -> Estimate
+{
+    opponents.opponents[&id].stats.wheel
+}
+
+#[test]
+fn wheel_level_keeps_the_most_moved_in_a_turn()
+{
+    let mut opponents = Opponents::default();
+
+    sight(&mut opponents, "1.........", TURN);
+    assert_eq!(wheel(&opponents, 1), Estimate::default());
+
+    sight(&mut opponents, "..1.......", TURN + 1);
+    sight(&mut opponents, "...1......", TURN + 2);
+    assert_eq!(
+        wheel(&opponents, 1),
+        Estimate {
+            level: 2,
+            confidence: Confidence::Certain,
+        }
+    );
+
+    // NOTE: Nothing moves faster than the last wheel level
+    sight(&mut opponents, ".........1", TURN + 3);
+    assert_eq!(wheel(&opponents, 1).level, rules::MAX_WHEEL_LEVEL);
+}
+
+#[test]
+fn wheel_level_averages_over_fog_gaps()
+{
+    let mut opponents = Opponents::default();
+
+    sight(&mut opponents, "1.........", TURN);
+    sight(&mut opponents, "??????????", TURN + 1);
+    assert!(!opponents.opponents[&1].up_to_date);
+
+    // NOTE: Five tiles over three turns took at least two a turn
+    sight(&mut opponents, ".....1....", TURN + 3);
+    assert_eq!(
+        wheel(&opponents, 1),
+        Estimate {
+            level: 2,
+            confidence: Confidence::Certain,
+        }
+    );
+
+    // NOTE: A slow stretch tells nothing new
+    sight(&mut opponents, "??????????", TURN + 4);
+    sight(&mut opponents, "......1...", TURN + 9);
+    assert_eq!(wheel(&opponents, 1).level, 2);
+}
+
+#[test]
+fn estimates_only_go_up()
+{
+    let mut estimate = Estimate::default();
+
+    estimate.observe(2, Confidence::Possible);
+    estimate.observe(1, Confidence::Certain);
+    assert_eq!(
+        estimate,
+        Estimate {
+            level: 2,
+            confidence: Confidence::Possible,
+        }
+    );
+
+    estimate.observe(2, Confidence::Certain);
+    assert_eq!(estimate.confidence, Confidence::Certain);
+    estimate.observe(2, Confidence::Possible);
+    assert_eq!(estimate.confidence, Confidence::Certain);
+}
@@ -0,0 +1,76 @@
+use crate::map;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Profile
+{
+    #[default]
+    Balanced,
+    Explorer,
+    Fighter,
+}
+
+pub const PROFILES: [Profile; 3] = [Profile::Balanced, Profile::Explorer, Profile::Fighter];
+
+impl Profile
+{
+    // NOTE: Exploring and fighting are all about
+    // getting somewhere in as few turns as possible
+    pub(crate) fn cost_model(self) -> map::CostModel
+    {
+        match self {
+            Profile::Balanced => map::CostModel::Weighted,
+            Profile::Explorer | Profile::Fighter => map::CostModel::Turns,
+        }
+    }
+}
+
+impl ::core::fmt::Display for Profile
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            Profile::Balanced => write!(f, "balanced"),
+            Profile::Explorer => write!(f, "explorer"),
+            Profile::Fighter => write!(f, "fighter"),
+        }
+    }
+}
+
+impl ::core::str::FromStr for Profile
+{
+    type Err = Error;
+
+    fn from_str(input: &str) -> ::core::result::Result<Self, Self::Err>
+    {
+        PROFILES
+            .into_iter()
+            .find(|profile| profile.to_string() == input)
+            .ok_or(Error::Unknown)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error
+{
+    Unknown,
+}
+
+impl ::core::fmt::Display for Error
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            Error::Unknown => write!(f, "unknown profile"),
+        }
+    }
+}
+
+impl ::core::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)>
+    {
+        match self {
+            Error::Unknown => None,
+        }
+    }
+}
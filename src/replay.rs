@@ -0,0 +1,331 @@
+pub const FORMAT_VERSION: usize = 1;
+pub const BOT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Header
+{
+    pub format_version: usize,
+    pub bot_version: String,
+    pub id: usize,
+    pub profile: crate::Profile,
+}
+
+impl Header
+{
+    pub fn new(id: usize, profile: crate::Profile) -> Self
+    {
+        Header {
+            format_version: FORMAT_VERSION,
+            bot_version: BOT_VERSION.into(),
+            id,
+            profile,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Turn
+{
+    pub round: usize,
+    pub elapsed: ::core::time::Duration,
+    // NOTE: The bot was started afresh on this turn, as when a
+    // resumed game keeps adding to an earlier session's recording
+    pub restart: bool,
+
+    pub input: String,
+    pub output: String,
+}
+
+pub fn show_header(
+    Header {
+        format_version,
+        bot_version,
+        id,
+        profile,
+    }: &Header,
+) -> String
+{
+    format!("replay {format_version}\nbot {bot_version}\nid {id}\nprofile {profile}\n")
+}
+
+pub fn show_turn(
+    Turn {
+        round,
+        elapsed,
+        restart,
+        input,
+        output,
+    }: &Turn,
+) -> String
+{
+    let mut shown = if *restart {
+        String::from("restart\n")
+    } else {
+        String::new()
+    };
+    shown.push_str(&format!("turn {round} {}\n", elapsed.as_micros()));
+
+    for (section, text) in [("input", input), ("output", output)] {
+        let lines = text.lines().collect::<Vec<_>>();
+
+        shown.push_str(&format!("{section} {}\n", lines.len()));
+        for line in lines {
+            shown.push_str(line);
+            shown.push('\n');
+        }
+    }
+
+    shown
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Replay
+{
+    pub header: Header,
+    pub turns: Vec<Turn>,
+}
+
+pub fn try_parse<In>(input: In) -> ::core::result::Result<Replay, Error>
+where
+    In: AsRef<str>,
+{
+    let mut lines = input.as_ref().lines().enumerate();
+
+    let mut field = |name: &'static str| {
+        let (index, line) = lines.next().ok_or(Error::Truncated)?;
+
+        line.strip_prefix(name)
+            .and_then(|value| value.strip_prefix(' '))
+            .ok_or(Error::Malformed { line: index + 1 })
+    };
+
+    let format_version = field("replay")?.parse()?;
+    if format_version != FORMAT_VERSION {
+        return Err(Error::UnsupportedVersion {
+            version: format_version,
+        });
+    }
+    let bot_version = field("bot")?.into();
+    let id = field("id")?.parse()?;
+    let profile = field("profile")?.parse()?;
+
+    let header = Header {
+        format_version,
+        bot_version,
+        id,
+        profile,
+    };
+
+    let mut turns = Vec::new();
+    while let Some((index, line)) = lines.next() {
+        let (restart, (index, line)) = match line {
+            "restart" => (true, lines.next().ok_or(Error::Truncated)?),
+            _ => (false, (index, line)),
+        };
+        let (round, elapsed) = line
+            .strip_prefix("turn ")
+            .and_then(|turn| turn.split_once(' '))
+            .ok_or(Error::Malformed { line: index + 1 })?;
+
+        let [input, output] = ["input", "output"].try_map(|section| {
+            let (index, line) = lines.next().ok_or(Error::Truncated)?;
+            let count = line
+                .strip_prefix(section)
+                .and_then(|count| count.strip_prefix(' '))
+                .ok_or(Error::Malformed { line: index + 1 })?
+                .parse::<usize>()?;
+
+            (0..count)
+                .map(|_| lines.next().map(|(_, line)| line).ok_or(Error::Truncated))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|text| text.join("\n"))
+        })?;
+
+        turns.push(Turn {
+            round: round.parse()?,
+            elapsed: ::core::time::Duration::from_micros(elapsed.parse()?),
+            restart,
+
+            input,
+            output,
+        });
+    }
+
+    Ok(Replay { header, turns })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Divergence
+{
+    pub round: usize,
+    pub recorded: String,
+    pub replayed: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Report
+{
+    pub turns: usize,
+    pub divergences: Vec<Divergence>,
+
+    pub recorded_elapsed: ::core::time::Duration,
+    pub replayed_elapsed: ::core::time::Duration,
+}
+
+pub fn run(
+    Replay {
+        header: Header { profile, .. },
+        turns,
+    }: &Replay,
+) -> ::core::result::Result<Report, crate::Error>
+{
+    let mut bot = None::<crate::Bot>;
+    let mut report = Report::default();
+
+    for Turn {
+        round,
+        elapsed,
+        restart,
+        input,
+        output,
+    } in turns
+    {
+        if *restart {
+            bot = None;
+        }
+
+        let started = ::std::time::Instant::now();
+        let replayed = play(&mut bot, *round, input, *profile)?;
+
+        report.turns += 1;
+        report.recorded_elapsed += *elapsed;
+        report.replayed_elapsed += started.elapsed();
+
+        if replayed != *output {
+            report.divergences.push(Divergence {
+                round: *round,
+                recorded: output.clone(),
+                replayed,
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+// NOTE: The bot as it was right after answering `round`,
+// `None` if the recording never got that far
+pub fn bot_after(
+    Replay {
+        header: Header { profile, .. },
+        turns,
+    }: &Replay,
+    round: usize,
+) -> ::core::result::Result<Option<crate::Bot>, crate::Error>
+{
+    let mut bot = None::<crate::Bot>;
+
+    for turn in turns {
+        if turn.restart {
+            bot = None;
+        }
+
+        let _replayed = play(&mut bot, turn.round, &turn.input, *profile)?;
+
+        if turn.round == round {
+            return Ok(bot);
+        }
+    }
+
+    Ok(None)
+}
+
+// NOTE: Recordings can start mid-game, the bot
+// starts out on whichever round comes first
+fn play(
+    bot: &mut Option<crate::Bot>,
+    round: usize,
+    input: &str,
+    profile: crate::Profile,
+) -> ::core::result::Result<String, crate::Error>
+{
+    match bot {
+        Some(ref mut bot) => bot.turn(input),
+        None => {
+            let (init_bot, next_turn) = crate::uninit::try_resume(input, profile, round, None)?;
+
+            let _ = bot.insert(init_bot);
+
+            Ok(next_turn)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error
+{
+    Truncated,
+    Malformed
+    {
+        line: usize,
+    },
+    UnsupportedVersion
+    {
+        version: usize,
+    },
+    ParseInt
+    {
+        parse_int_err: ::core::num::ParseIntError,
+    },
+    Profile
+    {
+        profile_err: crate::profile::Error,
+    },
+}
+
+impl ::core::fmt::Display for Error
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            Error::Truncated => write!(f, "truncated replay"),
+            Error::Malformed { line } => write!(f, "malformed replay on line {line}"),
+            Error::UnsupportedVersion { version } => {
+                write!(f, "unsupported replay format version {version}")
+            }
+            Error::ParseInt { parse_int_err } => write!(f, "{parse_int_err}"),
+            Error::Profile { profile_err } => write!(f, "{profile_err}"),
+        }
+    }
+}
+
+impl ::core::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)>
+    {
+        match self {
+            Error::ParseInt { parse_int_err } => Some(parse_int_err),
+            Error::Profile { profile_err } => Some(profile_err),
+            Error::Truncated | Error::Malformed { .. } | Error::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+impl From<::core::num::ParseIntError> for Error
+{
+    fn from(parse_int_err: ::core::num::ParseIntError) -> Self
+    {
+        Error::ParseInt { parse_int_err }
+    }
+}
+
+impl From<crate::profile::Error> for Error
+{
+    fn from(profile_err: crate::profile::Error) -> Self
+    {
+        Error::Profile { profile_err }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,162 @@
+use super::*;
+
+fn replay() -> Replay
+{
+    Replay {
+        header: Header::new(3, crate::Profile::Fighter),
+        turns: vec![
+            Turn {
+                round: 0,
+                elapsed: ::core::time::Duration::from_micros(1234),
+                restart: false,
+
+                input: "5 5\n0 0\n.....\n..0..\n.....".into(),
+                output: "U R\nM L\nB A".into(),
+            },
+            // NOTE: A turn the bot had nothing to say about, right
+            // after it got started afresh
+            Turn {
+                round: 1,
+                elapsed: ::core::time::Duration::ZERO,
+                restart: true,
+
+                input: "5 5\n0 1".into(),
+                output: String::new(),
+            },
+        ],
+    }
+}
+
+fn show(Replay { header, turns }: &Replay) -> String
+{
+    let mut shown = show_header(header);
+    for turn in turns {
+        shown.push_str(&show_turn(turn));
+    }
+
+    shown
+}
+
+#[test]
+fn parse_reads_back_what_show_wrote()
+{
+    let replay = replay();
+
+    assert_eq!(try_parse(show(&replay)), Ok(replay));
+}
+
+#[test]
+fn parse_rejects_other_format_versions()
+{
+    let shown = show(&replay()).replacen(
+        &format!("replay {FORMAT_VERSION}"),
+        &format!("replay {}", FORMAT_VERSION + 1),
+        1,
+    );
+
+    assert_eq!(
+        try_parse(shown),
+        Err(Error::UnsupportedVersion {
+            version: FORMAT_VERSION + 1
+        })
+    );
+}
+
+#[test]
+fn parse_reports_truncated_turns()
+{
+    let shown = show(&replay());
+    let shown = &shown[..shown.rfind("input").unwrap()];
+
+    assert_eq!(try_parse(shown), Err(Error::Truncated));
+}
+
+// NOTE: Recorded the way the driver does, the bot sitting out
+// the rounds before `start` and getting started afresh on `restarts`
+fn record(start: usize, rounds: usize, restarts: &[usize]) -> Vec<Turn>
+{
+    let mut simulator = crate::sim::Simulator::try_init(crate::sim::Config {
+        width: 20,
+        height: 20,
+        players: 2,
+        seed: 2,
+        rounds,
+    })
+    .unwrap();
+    let mut bot = None::<crate::Bot>;
+    let mut turns = Vec::new();
+
+    while !simulator.is_over() {
+        let round = simulator.round();
+        let input = simulator.view(0).unwrap();
+
+        let output = if round < start {
+            "M U".into()
+        } else {
+            let restart = restarts.contains(&round);
+            if restart {
+                bot = None;
+            }
+
+            let output = match bot {
+                Some(ref mut bot) => bot.turn(&input).unwrap(),
+                None => {
+                    let (resumed, output) =
+                        crate::uninit::try_resume(&input, crate::Profile::default(), round, None)
+                            .unwrap();
+                    bot = Some(resumed);
+
+                    output
+                }
+            };
+            turns.push(Turn {
+                round,
+                elapsed: ::core::time::Duration::ZERO,
+                restart,
+
+                input,
+                output: output.clone(),
+            });
+
+            output
+        };
+
+        simulator.try_submit(0, output).unwrap();
+        simulator.step();
+    }
+
+    turns
+}
+
+fn assert_replays(turns: Vec<Turn>)
+{
+    let recorded = turns.len();
+    assert!(recorded > 0);
+
+    let report = run(&Replay {
+        header: Header::new(0, crate::Profile::default()),
+        turns,
+    })
+    .unwrap();
+
+    assert_eq!(report.turns, recorded);
+    assert_eq!(report.divergences, Vec::new());
+}
+
+// NOTE: A session picked up late, like one given `--start-round`,
+// replays on the rounds it was recorded on
+#[test]
+fn run_replays_recordings_starting_mid_game()
+{
+    const START: usize = crate::constants::acid::START_TURN - 5;
+
+    assert_replays(record(START, START + 20, &[]));
+}
+
+// NOTE: A resumed game without a usable snapshot
+// adds a second session to the same recording
+#[test]
+fn run_restarts_the_bot_where_the_recording_says()
+{
+    assert_replays(record(0, 60, &[10]));
+}
@@ -0,0 +1,122 @@
+use crate::{
+    game,
+    io::output::{direction, upgrade},
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub(super) struct Commands
+{
+    pub(super) moves: Vec<game::Direction>,
+    pub(super) action: Option<game::Action>,
+    pub(super) upgrade: Option<game::Upgrade>,
+}
+
+pub(super) fn try_parse<In>(input: In) -> ::core::result::Result<Commands, Error>
+where
+    In: AsRef<str>,
+{
+    let mut commands = Commands::default();
+
+    for tokens in input
+        .as_ref()
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .filter(|tokens| !tokens.is_empty())
+    {
+        match tokens[..] {
+            ["B", upgrade] => {
+                if commands.upgrade.is_some() {
+                    return Err(Error::Duplicate {
+                        kind: Kind::Upgrade,
+                    });
+                }
+
+                commands.upgrade = Some(upgrade::try_parse(upgrade).ok_or(Error::Malformed {
+                    kind: Kind::Upgrade,
+                })?);
+            }
+            [action @ ("A" | "S" | "M" | "P"), direction] => {
+                if commands.action.is_some() {
+                    return Err(Error::Duplicate { kind: Kind::Action });
+                }
+
+                let direction = direction::try_parse(direction)
+                    .ok_or(Error::Malformed { kind: Kind::Action })?;
+
+                commands.action = Some(match action {
+                    "A" => game::Action::Attack { direction },
+                    "S" => game::Action::Scan { direction },
+                    "M" => game::Action::Mine { direction },
+                    _ => game::Action::Place { direction },
+                });
+            }
+            ref moves => {
+                if !commands.moves.is_empty() {
+                    return Err(Error::Duplicate { kind: Kind::Moves });
+                }
+
+                commands.moves = moves
+                    .iter()
+                    .map(|direction| direction::try_parse(direction))
+                    .collect::<Option<_>>()
+                    .ok_or(Error::Malformed { kind: Kind::Moves })?;
+            }
+        }
+    }
+
+    Ok(commands)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind
+{
+    Moves,
+    Action,
+    Upgrade,
+}
+
+impl ::core::fmt::Display for Kind
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            Kind::Moves => write!(f, "moves"),
+            Kind::Action => write!(f, "action"),
+            Kind::Upgrade => write!(f, "upgrade"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error
+{
+    Malformed
+    {
+        kind: Kind
+    },
+    Duplicate
+    {
+        kind: Kind
+    },
+}
+
+impl ::core::fmt::Display for Error
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            Error::Malformed { kind } => write!(f, "malformed {kind} command"),
+            Error::Duplicate { kind } => write!(f, "duplicate {kind} command"),
+        }
+    }
+}
+
+impl ::core::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)>
+    {
+        match self {
+            Error::Malformed { .. } | Error::Duplicate { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use ::std::collections;
+
+use crate::{
+    constants, game,
+    sim::{world, Config},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct Rng
+{
+    state: u64,
+}
+
+impl Rng
+{
+    pub(super) fn new(seed: u64) -> Self
+    {
+        Rng { state: seed }
+    }
+
+    // NOTE: SplitMix64, good enough for map generation
+    // and stable across platforms for a given seed
+    pub(super) fn next_u64(&mut self) -> u64
+    {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        z ^ (z >> 31)
+    }
+
+    pub(super) fn below(&mut self, bound: usize) -> usize
+    {
+        (self.next_u64() % bound as u64) as _
+    }
+}
+
+pub(super) fn generate(
+    &Config {
+        width,
+        height,
+        players,
+        seed,
+        ..
+    }: &Config,
+) -> world::World
+{
+    let mut rng = Rng::new(seed);
+    let dimensions = game::Dimensions { width, height };
+
+    let mut tiles = (0..width * height)
+        .map(|_| match rng.below(100) {
+            0..=2 => game::Tile::Bedrock,
+            3..=49 => game::Tile::Stone,
+            50..=57 => game::Tile::Iron,
+            _ => game::Tile::Air,
+        })
+        .collect::<Box<[_]>>();
+
+    // NOTE: The map generator places exactly
+    // one Osmium inside every chunk
+    let chunk_size = constants::rules::CHUNK_SIZE;
+    for chunk_y in (0..height).step_by(chunk_size) {
+        for chunk_x in (0..width).step_by(chunk_size) {
+            let x = chunk_x + rng.below(chunk_size.min(width - chunk_x));
+            let y = chunk_y + rng.below(chunk_size.min(height - chunk_y));
+
+            tiles[y * width + x] = game::Tile::Osmium;
+        }
+    }
+
+    let robots = bases(dimensions)
+        .into_iter()
+        .take(players as _)
+        .enumerate()
+        .map(|(id, base)| {
+            for y in base.y - 1..=base.y + 1 {
+                for x in base.x - 1..=base.x + 1 {
+                    let position = game::Position { x, y };
+
+                    if position.is_within_bounds(dimensions) {
+                        tiles[position.to_linear(width)] = game::Tile::Air;
+                    }
+                }
+            }
+            tiles[base.to_linear(width)] = game::Tile::Base;
+
+            let player = game::Player {
+                position: base,
+                stats: game::player::Stats {
+                    hit_points: constants::rules::MAX_HIT_POINTS,
+
+                    drill_level: 1,
+                    rifle_level: 1,
+                    wheel_level: 1,
+                    sight_level: 1,
+
+                    has_antenna: false,
+                    has_battery: false,
+                },
+                inventory: game::player::Inventory::default(),
+
+                base,
+            };
+
+            (id as _, world::Robot { player, scan: None })
+        })
+        .collect::<collections::BTreeMap<_, _>>();
+
+    world::World {
+        dimensions,
+        hits: tiles.iter().map(|_| 0).collect(),
+        tiles,
+
+        robots,
+        round: 0,
+    }
+}
+
+pub(super) const MAX_PLAYERS: u8 = 8;
+
+fn bases(game::Dimensions { width, height }: game::Dimensions)
+    -> [game::Position; MAX_PLAYERS as _]
+{
+    let (width, height) = (width as isize, height as isize);
+    let inset = 2;
+
+    let (left, top, right, bottom) = (inset, inset, width - 1 - inset, height - 1 - inset);
+    let (middle_x, middle_y) = (width / 2, height / 2);
+
+    [
+        (left, top),
+        (right, bottom),
+        (right, top),
+        (left, bottom),
+        (middle_x, top),
+        (middle_x, bottom),
+        (left, middle_y),
+        (right, middle_y),
+    ]
+    .map(|(x, y)| game::Position { x, y })
+}
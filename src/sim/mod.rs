@@ -0,0 +1,238 @@
+use ::std::collections;
+
+mod command;
+mod generate;
+mod render;
+mod world;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Config
+{
+    pub width: usize,
+    pub height: usize,
+    pub players: u8,
+
+    pub seed: u64,
+    pub rounds: usize,
+}
+
+impl Default for Config
+{
+    fn default() -> Self
+    {
+        Config {
+            width: 30,
+            height: 30,
+            players: 2,
+
+            seed: 0,
+            rounds: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simulator
+{
+    world: world::World,
+    pending: collections::BTreeMap<u8, command::Commands>,
+
+    eliminations: Vec<(u8, usize)>,
+    players: u8,
+    rounds: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Standing
+{
+    pub id: u8,
+    pub hit_points: u8,
+    pub eliminated_at: Option<usize>,
+}
+
+impl Simulator
+{
+    pub fn try_init(config: Config) -> ::core::result::Result<Self, Error>
+    {
+        let Config {
+            width,
+            height,
+            players,
+            ..
+        } = config;
+
+        if !(1..=generate::MAX_PLAYERS).contains(&players) {
+            return Err(Error::Config {
+                kind: ConfigKind::Players,
+            });
+        }
+        // NOTE: Bases sit two tiles away from the edges
+        // and need a tile of clearance around them
+        if width < 7 || height < 7 {
+            return Err(Error::Config {
+                kind: ConfigKind::Dimensions,
+            });
+        }
+
+        Ok(Simulator {
+            world: generate::generate(&config),
+            pending: collections::BTreeMap::new(),
+
+            eliminations: Vec::new(),
+            players,
+            rounds: config.rounds,
+        })
+    }
+
+    pub fn round(&self) -> usize
+    {
+        self.world.round
+    }
+
+    pub fn alive(&self) -> Vec<u8>
+    {
+        self.world.robots.keys().copied().collect()
+    }
+
+    pub fn view(&self, id: u8) -> Option<String>
+    {
+        render::view(&self.world, id)
+    }
+
+    pub fn try_submit<In>(&mut self, id: u8, commands: In) -> ::core::result::Result<(), Error>
+    where
+        In: AsRef<str>,
+    {
+        if !self.world.robots.contains_key(&id) {
+            return Err(Error::UnknownPlayer { id });
+        }
+
+        let commands = command::try_parse(commands)?;
+        let _previous = self.pending.insert(id, commands);
+
+        Ok(())
+    }
+
+    pub fn step(&mut self)
+    {
+        self.world.clear_scans();
+
+        // NOTE: Players act in increasing id order
+        for (id, commands) in ::core::mem::take(&mut self.pending) {
+            self.world.apply(id, commands);
+        }
+
+        let round = self.world.round;
+        self.eliminations
+            .extend(self.world.advance().into_iter().map(|id| (id, round)));
+    }
+
+    // NOTE: A solo game has no last robot standing to wait
+    // for, it runs until the rounds or the robot run out
+    pub fn is_over(&self) -> bool
+    {
+        let last_standing = match self.players {
+            1 => 0,
+            _ => 1,
+        };
+
+        self.world.round >= self.rounds || self.world.robots.len() <= last_standing
+    }
+
+    pub fn standings(&self) -> Vec<Standing>
+    {
+        let mut alive = self
+            .world
+            .robots
+            .iter()
+            .map(|(&id, robot)| Standing {
+                id,
+                hit_points: robot.player.stats.hit_points,
+                eliminated_at: None,
+            })
+            .collect::<Vec<_>>();
+        alive.sort_by_key(|Standing { hit_points, .. }| ::core::cmp::Reverse(*hit_points));
+
+        alive
+            .into_iter()
+            .chain(self.eliminations.iter().rev().map(|&(id, round)| Standing {
+                id,
+                hit_points: 0,
+                eliminated_at: Some(round),
+            }))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfigKind
+{
+    Players,
+    Dimensions,
+}
+
+impl ::core::fmt::Display for ConfigKind
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            ConfigKind::Players => write!(
+                f,
+                "player count must be between 1 and {}",
+                generate::MAX_PLAYERS
+            ),
+            ConfigKind::Dimensions => write!(f, "map must be at least 7 by 7"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error
+{
+    Config
+    {
+        kind: ConfigKind
+    },
+    UnknownPlayer
+    {
+        id: u8
+    },
+    Command
+    {
+        command_err: command::Error
+    },
+}
+
+impl ::core::fmt::Display for Error
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            Error::Config { kind } => write!(f, "invalid config, {kind}"),
+            Error::UnknownPlayer { id } => write!(f, "unknown player {id}"),
+            Error::Command { command_err } => write!(f, "{command_err}"),
+        }
+    }
+}
+
+impl ::core::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)>
+    {
+        match self {
+            Error::Command { command_err } => Some(command_err),
+            Error::Config { .. } | Error::UnknownPlayer { .. } => None,
+        }
+    }
+}
+
+impl From<command::Error> for Error
+{
+    fn from(command_err: command::Error) -> Self
+    {
+        Error::Command { command_err }
+    }
+}
+
+#[cfg(test)]
+mod tests;
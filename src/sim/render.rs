@@ -0,0 +1,69 @@
+use crate::{game, io::output::tile, sim::world};
+
+pub(super) fn view(world: &world::World, id: u8) -> Option<String>
+{
+    let world::Robot {
+        player:
+            game::Player {
+                position,
+                stats,
+                inventory,
+                ..
+            },
+        scan,
+    } = *world.robots.get(&id)?;
+
+    let game::Dimensions { width, height } = world.dimensions;
+    let radius = world::sight_radius(stats.sight_level);
+
+    let rows = (0..height as isize).map(|y| {
+        (0..width as isize)
+            .map(|x| {
+                let tile_position = game::Position { x, y };
+
+                let visible = position.chebyshev_distance(&tile_position) <= radius
+                    || scan.is_some_and(|scan| scan.reveals(tile_position));
+
+                match world.robot_at(tile_position) {
+                    Some(robot_id) if visible || stats.has_antenna => {
+                        tile::show(game::Tile::Player { id: robot_id })
+                    }
+                    _ if visible => world.tile_at(tile_position).map_or('?', tile::show),
+                    _ => tile::show(game::Tile::Fog),
+                }
+            })
+            .intersperse(' ')
+            .collect::<String>()
+    });
+
+    let game::Position { x, y } = position;
+    let game::player::Stats {
+        hit_points,
+        drill_level,
+        rifle_level,
+        wheel_level,
+        sight_level,
+        has_antenna,
+        has_battery,
+    } = stats;
+    let game::player::Inventory {
+        stone,
+        iron,
+        osmium,
+    } = inventory;
+
+    let lines = ::core::iter::once(format!("{width} {height}"))
+        .chain(rows)
+        .chain([
+            format!("{x} {y}"),
+            format!(
+                "{hit_points} {drill_level} {rifle_level} {wheel_level} {sight_level} {} {}",
+                has_antenna as u8, has_battery as u8
+            ),
+            format!("{stone} {iron} {osmium}"),
+        ])
+        .intersperse("\n".into())
+        .collect();
+
+    Some(lines)
+}
@@ -0,0 +1,296 @@
+use super::*;
+use crate::{constants, game, io::input::map::tile};
+
+// NOTE: Digits are robots fresh off the start, standing on air
+fn simulator(rows: &[&str]) -> Simulator
+{
+    let dimensions = game::Dimensions {
+        width: rows[0].len(),
+        height: rows.len(),
+    };
+
+    let mut tiles = Vec::new();
+    let mut robots = collections::BTreeMap::new();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, byte) in row.bytes().enumerate() {
+            let position = game::Position {
+                x: x as _,
+                y: y as _,
+            };
+
+            tiles.push(match tile::try_parse(byte).unwrap() {
+                game::Tile::Player { id } => {
+                    let player = game::Player {
+                        position,
+                        stats: game::player::Stats {
+                            hit_points: constants::rules::MAX_HIT_POINTS,
+
+                            drill_level: 1,
+                            rifle_level: 1,
+                            wheel_level: 1,
+                            sight_level: 1,
+
+                            has_antenna: false,
+                            has_battery: false,
+                        },
+                        inventory: game::player::Inventory::default(),
+
+                        base: position,
+                    };
+                    let _previous = robots.insert(id, world::Robot { player, scan: None });
+
+                    game::Tile::Air
+                }
+                tile => tile,
+            });
+        }
+    }
+
+    let players = robots.len() as u8;
+
+    Simulator {
+        world: world::World {
+            dimensions,
+            hits: tiles.iter().map(|_| 0).collect(),
+            tiles: tiles.into(),
+
+            robots,
+            round: 0,
+        },
+        pending: collections::BTreeMap::new(),
+
+        eliminations: Vec::new(),
+        players,
+        rounds: constants::acid::START_TURN * 2,
+    }
+}
+
+fn play(simulator: &mut Simulator, commands: &[(u8, &str)])
+{
+    for &(id, commands) in commands {
+        simulator.try_submit(id, commands).unwrap();
+    }
+
+    simulator.step();
+}
+
+fn player(simulator: &Simulator, id: u8) -> game::Player
+{
+    simulator.world.robots[&id].player
+}
+
+fn tile(simulator: &Simulator, x: isize, y: isize) -> Option<game::Tile>
+{
+    simulator.world.tile_at(game::Position { x, y })
+}
+
+#[test]
+fn rules_for_moves_mining_placing_attacks_and_acid()
+{
+    let mut simulator = simulator(&[
+        ".........",
+        ".........",
+        "..0.X....",
+        ".........",
+        "2........",
+        ".........",
+        "....C....",
+        ".........",
+        "....1....",
+    ]);
+
+    // NOTE: Wheels cap the moves taken, and stone at the
+    // first drill level takes two hits to come out
+    play(&mut simulator, &[(0, "R\nM R"), (1, "U U\nM U")]);
+    assert_eq!(
+        player(&simulator, 0).position,
+        game::Position { x: 3, y: 2 }
+    );
+    assert_eq!(tile(&simulator, 4, 2), Some(game::Tile::Stone));
+    assert_eq!(
+        player(&simulator, 1).position,
+        game::Position { x: 4, y: 7 }
+    );
+    assert_eq!(tile(&simulator, 4, 6), Some(game::Tile::Air));
+    assert_eq!(player(&simulator, 1).inventory.iron, 1);
+
+    play(&mut simulator, &[(0, "M R"), (1, "U")]);
+    assert_eq!(tile(&simulator, 4, 2), Some(game::Tile::Air));
+    assert_eq!(player(&simulator, 0).inventory.stone, 1);
+
+    // NOTE: Blocks go down after moving, and cost the stone
+    play(&mut simulator, &[(0, "R\nP L"), (1, "U")]);
+    assert_eq!(
+        player(&simulator, 0).position,
+        game::Position { x: 4, y: 2 }
+    );
+    assert_eq!(tile(&simulator, 3, 2), Some(game::Tile::Cobblestone));
+    assert_eq!(player(&simulator, 0).inventory.stone, 0);
+
+    play(&mut simulator, &[(1, "U\nA U")]);
+    assert_eq!(
+        player(&simulator, 1).position,
+        game::Position { x: 4, y: 4 }
+    );
+    assert_eq!(
+        player(&simulator, 0).stats.hit_points,
+        constants::rules::MAX_HIT_POINTS
+            - constants::rules::level_get(&constants::rules::RIFLE_DAMAGES, 1)
+    );
+
+    play(&mut simulator, &[(0, "P D")]);
+    assert_eq!(
+        tile(&simulator, 4, 3),
+        Some(game::Tile::Air),
+        "no stone left"
+    );
+
+    // NOTE: Obstacles stop both moves and shots
+    play(&mut simulator, &[(0, "M L")]);
+    play(&mut simulator, &[(0, "M L")]);
+    play(&mut simulator, &[(0, "P D")]);
+    assert_eq!(tile(&simulator, 4, 3), Some(game::Tile::Cobblestone));
+    let hit_points = player(&simulator, 0).stats.hit_points;
+    play(&mut simulator, &[(1, "U\nA U")]);
+    assert_eq!(
+        player(&simulator, 1).position,
+        game::Position { x: 4, y: 4 }
+    );
+    assert_eq!(player(&simulator, 0).stats.hit_points, hit_points);
+
+    // NOTE: Acid takes the outermost ring first,
+    // hurting whoever is standing in it
+    simulator.world.round = constants::acid::START_TURN - 1;
+    play(&mut simulator, &[]);
+    assert_eq!(tile(&simulator, 0, 0), Some(game::Tile::Acid));
+    assert_eq!(tile(&simulator, 1, 1), Some(game::Tile::Air));
+    assert_eq!(
+        player(&simulator, 2).stats.hit_points,
+        constants::rules::MAX_HIT_POINTS - constants::rules::ACID_DAMAGE
+    );
+    assert_eq!(
+        player(&simulator, 0).stats.hit_points,
+        hit_points,
+        "away from the border"
+    );
+}
+
+fn row(simulator: &Simulator, id: u8, y: usize) -> String
+{
+    simulator
+        .view(id)
+        .unwrap()
+        .lines()
+        .nth(y + 1)
+        .unwrap()
+        .replace(' ', "")
+}
+
+#[test]
+fn view_shows_sight_scans_and_antenna()
+{
+    let mut simulator = simulator(&[
+        "...........",
+        "0...X...C.1",
+        "...........",
+        "...........",
+        "...........",
+        "...........",
+    ]);
+
+    // NOTE: The first sight level sees three tiles around
+    assert_eq!(row(&simulator, 0, 1), "0...???????");
+    assert_eq!(row(&simulator, 0, 4), "....???????");
+    assert_eq!(row(&simulator, 0, 5), "???????????");
+
+    // NOTE: Scans light up a single lane, for the round they're made on
+    play(&mut simulator, &[(0, "S R")]);
+    assert_eq!(row(&simulator, 0, 1), "0...X...C??");
+    assert_eq!(row(&simulator, 0, 0), "....???????");
+
+    play(&mut simulator, &[]);
+    assert_eq!(row(&simulator, 0, 1), "0...???????");
+
+    // NOTE: Antennas show robots through fog, nothing else
+    simulator
+        .world
+        .robots
+        .get_mut(&0)
+        .unwrap()
+        .player
+        .stats
+        .has_antenna = true;
+    assert_eq!(row(&simulator, 0, 1), "0...??????1");
+}
+
+#[test]
+fn upgrades_take_resources_at_base_or_with_a_battery()
+{
+    let mut simulator = simulator(&["0....", "....."]);
+    simulator.world.robots.get_mut(&0).unwrap().player.inventory = game::player::Inventory {
+        stone: 0,
+        iron: 6,
+        osmium: 2,
+    };
+
+    play(&mut simulator, &[(0, "B S")]);
+    assert_eq!(player(&simulator, 0).stats.sight_level, 2);
+    assert_eq!(player(&simulator, 0).inventory.iron, 3);
+
+    // NOTE: The next level costs more than what's left
+    play(&mut simulator, &[(0, "B S")]);
+    assert_eq!(player(&simulator, 0).stats.sight_level, 2);
+    assert_eq!(player(&simulator, 0).inventory.iron, 3);
+
+    play(&mut simulator, &[(0, "R\nB B")]);
+    assert!(!player(&simulator, 0).stats.has_battery, "away from base");
+
+    play(&mut simulator, &[(0, "L\nB B")]);
+    assert!(player(&simulator, 0).stats.has_battery);
+
+    play(&mut simulator, &[(0, "R\nB R")]);
+    assert!(player(&simulator, 0).stats.has_antenna, "with a battery");
+    assert_eq!(
+        player(&simulator, 0).inventory,
+        game::player::Inventory::default()
+    );
+
+    let robot = simulator.world.robots.get_mut(&0).unwrap();
+    robot.player.stats.hit_points = 3;
+    robot.player.inventory.osmium = 1;
+    play(&mut simulator, &[(0, "B H")]);
+    assert_eq!(
+        player(&simulator, 0).stats.hit_points,
+        constants::rules::MAX_HIT_POINTS
+    );
+    assert_eq!(player(&simulator, 0).inventory.osmium, 0);
+}
+
+#[test]
+fn games_end_on_the_last_robot_standing_or_the_round_limit()
+{
+    let mut duel = simulator(&["0.1"]);
+    while !duel.is_over() {
+        play(&mut duel, &[(0, "A R")]);
+    }
+    assert_eq!(duel.alive(), vec![0]);
+    assert_eq!(
+        duel.round(),
+        constants::rules::MAX_HIT_POINTS as usize
+            / constants::rules::level_get(&constants::rules::RIFLE_DAMAGES, 1) as usize
+    );
+
+    // NOTE: Solo games have no one else to outlast
+    let mut solo = Simulator::try_init(Config {
+        players: 1,
+        rounds: 3,
+        ..Config::default()
+    })
+    .unwrap();
+    assert!(!solo.is_over());
+    for _ in 0..3 {
+        play(&mut solo, &[]);
+    }
+    assert!(solo.is_over());
+    assert_eq!(solo.alive(), vec![0]);
+}
@@ -0,0 +1,346 @@
+use ::std::collections;
+
+use crate::{
+    constants,
+    game::{self, upgrade},
+    sim::command,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct World
+{
+    pub(super) dimensions: game::Dimensions,
+    pub(super) tiles: Box<[game::Tile]>,
+    pub(super) hits: Box<[u8]>,
+
+    pub(super) robots: collections::BTreeMap<u8, Robot>,
+    pub(super) round: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(super) struct Robot
+{
+    pub(super) player: game::Player,
+    pub(super) scan: Option<Scan>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct Scan
+{
+    pub(super) from: game::Position,
+    pub(super) direction: game::Direction,
+}
+
+impl Scan
+{
+    pub(super) fn reveals(&self, position: game::Position) -> bool
+    {
+        let game::Position { x, y } = self.from;
+
+        let distance = match self.direction {
+            game::Direction::Right if position.y == y => position.x - x,
+            game::Direction::Left if position.y == y => x - position.x,
+            game::Direction::Up if position.x == x => y - position.y,
+            game::Direction::Down if position.x == x => position.y - y,
+            _ => return false,
+        };
+
+        (1..=constants::rules::SCAN_RANGE as isize).contains(&distance)
+    }
+}
+
+impl World
+{
+    pub(super) fn tile_at(&self, position: game::Position) -> Option<game::Tile>
+    {
+        if position.is_within_bounds(self.dimensions) {
+            self.tiles
+                .get(position.to_linear(self.dimensions.width))
+                .copied()
+        } else {
+            None
+        }
+    }
+
+    fn set_tile_at(&mut self, position: game::Position, tile: game::Tile)
+    {
+        let index = position.to_linear(self.dimensions.width);
+
+        if let (Some(current), Some(hits)) = (self.tiles.get_mut(index), self.hits.get_mut(index)) {
+            *current = tile;
+            *hits = 0;
+        }
+    }
+
+    pub(super) fn robot_at(&self, position: game::Position) -> Option<u8>
+    {
+        self.robots
+            .iter()
+            .find(|(_, robot)| robot.player.position == position)
+            .map(|(id, _)| *id)
+    }
+
+    fn is_walkable(&self, position: game::Position) -> bool
+    {
+        matches!(
+            self.tile_at(position),
+            Some(game::Tile::Air | game::Tile::Base | game::Tile::Acid)
+        ) && self.robot_at(position).is_none()
+    }
+
+    pub(super) fn apply(
+        &mut self,
+        id: u8,
+        command::Commands {
+            moves,
+            action,
+            upgrade,
+        }: command::Commands,
+    )
+    {
+        let Some(mut robot) = self.robots.get(&id).copied() else {
+            return;
+        };
+
+        for direction in moves.into_iter().take(robot.player.stats.wheel_level as _) {
+            let target = robot.player.position + direction;
+
+            if self.is_walkable(target) {
+                robot.player.position = target;
+                // NOTE: Moving ahead of the action keeps
+                // `robot_at` consistent for the action
+                let _previous = self.robots.insert(id, robot);
+            } else {
+                break;
+            }
+        }
+
+        if let Some(action) = action {
+            self.act(&mut robot, action);
+        }
+
+        if let Some(upgrade) = upgrade {
+            Self::upgrade(&mut robot, upgrade);
+        }
+
+        let _previous = self.robots.insert(id, robot);
+    }
+
+    fn act(&mut self, robot: &mut Robot, action: game::Action)
+    {
+        let position = robot.player.position;
+
+        match action {
+            game::Action::Mine { direction } => {
+                let target = position + direction;
+                let index = target.to_linear(self.dimensions.width);
+
+                match self.tile_at(target) {
+                    Some(game::Tile::Stone | game::Tile::Cobblestone) => {
+                        let required_hits = constants::rules::level_get(
+                            &constants::rules::STONE_HITS,
+                            robot.player.stats.drill_level,
+                        );
+
+                        // SAFETY: `tile_at` only succeeds for in-bounds positions
+                        let hits = unsafe { self.hits.get_unchecked_mut(index) };
+                        *hits += 1;
+
+                        if *hits >= required_hits {
+                            self.set_tile_at(target, game::Tile::Air);
+                            robot.player.inventory.stone += 1;
+                        }
+                    }
+                    Some(game::Tile::Iron) => {
+                        self.set_tile_at(target, game::Tile::Air);
+                        robot.player.inventory.iron += 1;
+                    }
+                    Some(game::Tile::Osmium) => {
+                        self.set_tile_at(target, game::Tile::Air);
+                        robot.player.inventory.osmium += 1;
+                    }
+                    _ => {}
+                }
+            }
+            game::Action::Place { direction } => {
+                let target = position + direction;
+
+                if robot.player.inventory.stone > 0
+                    && self.tile_at(target) == Some(game::Tile::Air)
+                    && self.robot_at(target).is_none()
+                {
+                    self.set_tile_at(target, game::Tile::Cobblestone);
+                    robot.player.inventory.stone -= 1;
+                }
+            }
+            game::Action::Attack { direction } => {
+                let range = constants::rules::level_get(
+                    &constants::rules::RIFLE_RANGES,
+                    robot.player.stats.rifle_level,
+                );
+                let damage = constants::rules::level_get(
+                    &constants::rules::RIFLE_DAMAGES,
+                    robot.player.stats.rifle_level,
+                );
+
+                let mut target = position;
+                for _ in 0..range {
+                    target = target + direction;
+
+                    if let Some(id) = self.robot_at(target) {
+                        if let Some(victim) = self.robots.get_mut(&id) {
+                            victim.player.stats.hit_points =
+                                victim.player.stats.hit_points.saturating_sub(damage);
+                        }
+
+                        break;
+                    }
+
+                    if !matches!(
+                        self.tile_at(target),
+                        Some(game::Tile::Air | game::Tile::Base | game::Tile::Acid)
+                    ) {
+                        break;
+                    }
+                }
+            }
+            game::Action::Scan { direction } => {
+                robot.scan = Some(Scan {
+                    from: position,
+                    direction,
+                });
+            }
+        }
+    }
+
+    fn upgrade(robot: &mut Robot, upgrade: game::Upgrade)
+    {
+        if !robot.player.can_upgrade() {
+            return;
+        }
+
+        let game::Player {
+            ref mut stats,
+            ref mut inventory,
+            ..
+        } = robot.player;
+
+        let Some(upgrade::Cost { iron, osmium }) = upgrade.cost(stats) else {
+            return;
+        };
+
+        if inventory.iron < iron || inventory.osmium < osmium {
+            return;
+        }
+
+        use game::Upgrade as U;
+
+        let applied = match upgrade {
+            // NOTE: `Upgrade::cost` has already turned
+            // down levels past the last one
+            U::Sight => level_up(&mut stats.sight_level),
+            U::Rifle => level_up(&mut stats.rifle_level),
+            U::Drill => level_up(&mut stats.drill_level),
+            U::Wheel => level_up(&mut stats.wheel_level),
+
+            U::Antenna => !::core::mem::replace(&mut stats.has_antenna, true),
+            U::Battery => !::core::mem::replace(&mut stats.has_battery, true),
+
+            U::Heal => {
+                stats.hit_points = constants::rules::MAX_HIT_POINTS;
+
+                true
+            }
+        };
+
+        if applied {
+            inventory.iron -= iron;
+            inventory.osmium -= osmium;
+        }
+    }
+
+    pub(super) fn clear_scans(&mut self)
+    {
+        for robot in self.robots.values_mut() {
+            robot.scan = None;
+        }
+    }
+
+    pub(super) fn advance(&mut self) -> Vec<u8>
+    {
+        self.round += 1;
+
+        let level = acid_level(self.round);
+        self.paint_acid(level);
+
+        let acid_positions = self
+            .robots
+            .iter()
+            .filter(|(_, robot)| self.tile_at(robot.player.position) == Some(game::Tile::Acid))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in acid_positions {
+            if let Some(robot) = self.robots.get_mut(&id) {
+                robot.player.stats.hit_points = robot
+                    .player
+                    .stats
+                    .hit_points
+                    .saturating_sub(constants::rules::ACID_DAMAGE);
+            }
+        }
+
+        let eliminated = self
+            .robots
+            .iter()
+            .filter(|(_, robot)| robot.player.stats.hit_points == 0)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in &eliminated {
+            let _robot = self.robots.remove(id);
+        }
+
+        eliminated
+    }
+
+    fn paint_acid(&mut self, level: usize)
+    {
+        let game::Dimensions { width, height } = self.dimensions;
+        let level = level.min(width.min(height) / 2);
+
+        for y in 0..height {
+            for x in 0..width {
+                let border_distance = x.min(y).min(width - 1 - x).min(height - 1 - y);
+
+                if border_distance < level {
+                    let position = game::Position {
+                        x: x as _,
+                        y: y as _,
+                    };
+
+                    self.set_tile_at(position, game::Tile::Acid);
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn acid_level(round: usize) -> usize
+{
+    if round >= constants::acid::START_TURN {
+        (round - constants::acid::START_TURN) / constants::acid::TICK_RATE + 1
+    } else {
+        0
+    }
+}
+
+fn level_up(level: &mut u8) -> bool
+{
+    *level += 1;
+
+    true
+}
+
+pub(super) fn sight_radius(sight_level: u8) -> usize
+{
+    constants::rules::level_get(&constants::rules::SIGHT_RADII, sight_level)
+}
@@ -0,0 +1,574 @@
+use ::std::collections;
+
+use crate::{
+    caging, chunks, game,
+    io::{
+        input::map::tile,
+        output::{self, direction, upgrade},
+    },
+    map, opponents, trip,
+};
+
+pub const FORMAT_VERSION: usize = 1;
+
+pub fn show(
+    crate::Bot {
+        map:
+            map::Map {
+                dimensions: game::Dimensions { width, height },
+                entries,
+                ..
+            },
+        chunks:
+            chunks::Chunks {
+                dimensions:
+                    game::Dimensions {
+                        width: chunks_width,
+                        height: chunks_height,
+                    },
+                osmium: chunk_osmium,
+            },
+        player:
+            game::Player {
+                position,
+                stats:
+                    game::player::Stats {
+                        hit_points,
+                        drill_level,
+                        rifle_level,
+                        wheel_level,
+                        sight_level,
+                        has_antenna,
+                        has_battery,
+                    },
+                inventory:
+                    game::player::Inventory {
+                        stone,
+                        iron,
+                        osmium,
+                    },
+                base,
+            },
+        opponents: opponents::Opponents { opponents },
+        events: _,
+        turn,
+        profile,
+        upgrade_queue_index,
+        trip:
+            trip::Trip {
+                upgrade: trip_upgrade,
+                stops,
+                known,
+                planned_at,
+            },
+        cage: caging::Cage { entryway, step },
+    }: &crate::Bot,
+) -> String
+{
+    let mut shown = format!(
+        "snapshot {FORMAT_VERSION}\nturn {turn}\nprofile {profile}\nupgrade {upgrade_queue_index}\n"
+    );
+
+    shown.push_str(&match entryway {
+        Some(caging::Entryway {
+            center_direction,
+            entry,
+        }) => format!(
+            "cage {step} {} {} {}\n",
+            direction::show(*center_direction),
+            entry.x,
+            entry.y
+        ),
+        None => format!("cage {step}\n"),
+    });
+
+    shown.push_str(&format!(
+        "player {} {} {} {}\n",
+        position.x, position.y, base.x, base.y
+    ));
+    shown.push_str(&format!(
+        "stats {hit_points} {drill_level} {rifle_level} {wheel_level} {sight_level} {} {}\n",
+        *has_antenna as u8, *has_battery as u8
+    ));
+    shown.push_str(&format!("inventory {stone} {iron} {osmium}\n"));
+
+    // NOTE: Sorted so equal bots always give equal snapshots
+    let mut opponents = opponents.values().collect::<Vec<_>>();
+    opponents.sort_by_key(|opponents::Opponent { id, .. }| *id);
+    shown.push_str(&format!("opponents {}\n", opponents.len()));
+    for opponents::Opponent {
+        id,
+        position,
+        stats:
+            opponents::Stats {
+                rifle:
+                    opponents::Estimate {
+                        level: rifle_level,
+                        confidence: rifle_confidence,
+                    },
+                wheel:
+                    opponents::Estimate {
+                        level: wheel_level,
+                        confidence: wheel_confidence,
+                    },
+            },
+        up_to_date,
+        last_seen,
+    } in opponents
+    {
+        shown.push_str(&format!(
+            "{id} {} {} {rifle_level} {} {wheel_level} {} {} {last_seen}\n",
+            position.x,
+            position.y,
+            confidence::show(*rifle_confidence),
+            confidence::show(*wheel_confidence),
+            *up_to_date as u8
+        ));
+    }
+
+    shown.push_str(&format!("map {width} {height}\n"));
+    for row in entries.chunks(*width) {
+        shown.extend(
+            row.iter()
+                .map(|map::Entry { tile, .. }| output::tile::show(*tile)),
+        );
+        shown.push('\n');
+    }
+
+    shown.push_str("seen\n");
+    for row in entries.chunks(*width) {
+        shown.extend(
+            row.iter()
+                .map(|map::Entry { last_seen, .. }| match last_seen {
+                    Some(turn) => turn.to_string(),
+                    None => "-".into(),
+                })
+                .intersperse(" ".into()),
+        );
+        shown.push('\n');
+    }
+
+    shown.push_str(&format!("chunks {chunks_width} {chunks_height}\n"));
+    for row in chunk_osmium.chunks(*chunks_width) {
+        shown.extend(row.iter().map(|osmium| match osmium {
+            chunks::Osmium::Unknown => '?',
+            chunks::Osmium::Found => 'F',
+            chunks::Osmium::Mined => 'M',
+        }));
+        shown.push('\n');
+    }
+
+    // NOTE: Sorted like the opponents
+    let mut known = known.iter().collect::<Vec<_>>();
+    known.sort_by_key(|game::Position { x, y }| (*y, *x));
+    shown.push_str(&format!(
+        "trip {} {planned_at} {} {}\n",
+        trip_upgrade.map_or('-', upgrade::show),
+        stops.len(),
+        known.len()
+    ));
+    for game::Position { x, y } in stops.iter().chain(known) {
+        shown.push_str(&format!("{x} {y}\n"));
+    }
+
+    shown
+}
+
+pub fn try_parse<In>(input: In) -> ::core::result::Result<crate::Bot, Error>
+where
+    In: AsRef<str>,
+{
+    let mut lines = input.as_ref().lines().enumerate();
+
+    let (_, version) = field(&mut lines, "snapshot")?;
+    let version = version.join(" ").parse()?;
+    if version != FORMAT_VERSION {
+        return Err(Error::UnsupportedVersion { version });
+    }
+
+    let [turn]: [usize; 1] = numbers(field(&mut lines, "turn")?)?;
+    let (line, profile) = field(&mut lines, "profile")?;
+    let profile: crate::Profile = profile
+        .join(" ")
+        .parse()
+        .map_err(|_| Error::Malformed { line })?;
+    let [upgrade_queue_index] = numbers(field(&mut lines, "upgrade")?)?;
+
+    let (cage_line, cage) = field(&mut lines, "cage")?;
+    let cage = match cage[..] {
+        [step] => caging::Cage {
+            entryway: None,
+            step: step.parse()?,
+        },
+        [step, center_direction, x, y] => caging::Cage {
+            entryway: Some(caging::Entryway {
+                center_direction: direction::try_parse(center_direction)
+                    .ok_or(Error::Malformed { line: cage_line })?,
+                entry: game::Position {
+                    x: x.parse()?,
+                    y: y.parse()?,
+                },
+            }),
+            step: step.parse()?,
+        },
+        _ => return Err(Error::Malformed { line: cage_line }),
+    };
+
+    let (player_line, values) = field(&mut lines, "player")?;
+    let [x, y, base_x, base_y] = numbers((player_line, values))?;
+    let [hit_points, drill_level, rifle_level, wheel_level, sight_level, has_antenna, has_battery] =
+        numbers(field(&mut lines, "stats")?)?;
+    let [stone, iron, osmium] = numbers(field(&mut lines, "inventory")?)?;
+
+    let player = game::Player {
+        position: game::Position { x, y },
+        stats: game::player::Stats {
+            hit_points,
+
+            drill_level,
+            rifle_level,
+            wheel_level,
+            sight_level,
+
+            has_antenna: has_antenna != 0,
+            has_battery: has_battery != 0,
+        },
+        inventory: game::player::Inventory {
+            stone,
+            iron,
+            osmium,
+        },
+
+        base: game::Position {
+            x: base_x,
+            y: base_y,
+        },
+    };
+
+    let [opponent_count] = numbers(field(&mut lines, "opponents")?)?;
+    let opponents = (0..opponent_count)
+        .map(|_| {
+            let (index, line) = lines.next().ok_or(Error::Truncated)?;
+            let [id, x, y, rifle_level, rifle_confidence, wheel_level, wheel_confidence, up_to_date, last_seen] =
+                line.split(' ')
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .map_err(|_| Error::Malformed { line: index + 1 })?;
+            let id = id.parse()?;
+
+            Ok((
+                id,
+                opponents::Opponent {
+                    id,
+                    position: game::Position {
+                        x: x.parse()?,
+                        y: y.parse()?,
+                    },
+                    stats: opponents::Stats {
+                        rifle: opponents::Estimate {
+                            level: rifle_level.parse()?,
+                            confidence: confidence::try_parse(rifle_confidence.parse()?)
+                                .ok_or(Error::Malformed { line: index + 1 })?,
+                        },
+                        wheel: opponents::Estimate {
+                            level: wheel_level.parse()?,
+                            confidence: confidence::try_parse(wheel_confidence.parse()?)
+                                .ok_or(Error::Malformed { line: index + 1 })?,
+                        },
+                    },
+
+                    up_to_date: up_to_date.parse::<u8>()? != 0,
+                    last_seen: last_seen.parse()?,
+                },
+            ))
+        })
+        .collect::<Result<collections::HashMap<_, _>, Error>>()?;
+    let opponents = opponents::Opponents { opponents };
+
+    let (map_line, values) = field(&mut lines, "map")?;
+    let [width, height] = numbers((map_line, values))?;
+    let dimensions = game::Dimensions { width, height };
+    if width == 0 || height == 0 {
+        return Err(Error::Malformed { line: map_line });
+    }
+    // NOTE: Everything the bot steps on or heads for has to be on the
+    // map, the flood-fill takes the player's position on trust
+    if !player.position.is_within_bounds(dimensions) || !player.base.is_within_bounds(dimensions) {
+        return Err(Error::Malformed { line: player_line });
+    }
+    if cage
+        .entryway
+        .is_some_and(|caging::Entryway { entry, .. }| !entry.is_within_bounds(dimensions))
+    {
+        return Err(Error::Malformed { line: cage_line });
+    }
+    let entries = (0..height)
+        .map(|_| {
+            let (index, row) = lines.next().ok_or(Error::Truncated)?;
+
+            if row.len() != width {
+                return Err(Error::Malformed { line: index + 1 });
+            }
+
+            row.bytes()
+                .map(|tile| tile::try_parse(tile).map(map::Entry::init))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(Error::from)
+        })
+        .collect::<Result<Vec<_>, Error>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let (line, seen) = lines.next().ok_or(Error::Truncated)?;
+    if seen != "seen" {
+        return Err(Error::Malformed { line: line + 1 });
+    }
+
+    let last_seen = (0..height)
+        .map(|_| {
+            let (index, row) = lines.next().ok_or(Error::Truncated)?;
+            let row = row
+                .split(' ')
+                .map(|last_seen| match last_seen {
+                    "-" => Ok(None),
+                    turn => turn.parse().map(Some),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if row.len() != width {
+                return Err(Error::Malformed { line: index + 1 });
+            }
+
+            Ok(row)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let entries = entries
+        .into_iter()
+        .zip(last_seen.into_iter().flatten())
+        .map(|(mut entry, last_seen)| {
+            entry.last_seen = last_seen;
+
+            entry
+        })
+        .collect::<Box<_>>();
+
+    let mut map = map::Map::new(dimensions, entries, profile.cost_model());
+    // NOTE: Distances aren't part of the snapshot, they are cheaper
+    // to recompute than to store, as of the turn that last computed them
+    map.update_danger(&opponents);
+    map.flood_fill(
+        player.position,
+        map::Traveller {
+            wheel_level: player.stats.wheel_level,
+            drill_level: player.stats.drill_level,
+            turn: turn.saturating_sub(1),
+        },
+    );
+
+    let mut chunks = chunks::Chunks::init(dimensions);
+    let (line, values) = field(&mut lines, "chunks")?;
+    let [width, height] = numbers((line, values))?;
+    if (width, height) != (chunks.dimensions.width, chunks.dimensions.height) {
+        return Err(Error::Malformed { line });
+    }
+
+    chunks.osmium = (0..height)
+        .map(|_| {
+            let (index, row) = lines.next().ok_or(Error::Truncated)?;
+
+            if row.len() != width {
+                return Err(Error::Malformed { line: index + 1 });
+            }
+
+            row.bytes()
+                .map(|osmium| match osmium {
+                    b'?' => Ok(chunks::Osmium::Unknown),
+                    b'F' => Ok(chunks::Osmium::Found),
+                    b'M' => Ok(chunks::Osmium::Mined),
+                    _ => Err(Error::Malformed { line: index + 1 }),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, Error>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let (line, values) = field(&mut lines, "trip")?;
+    let [trip_upgrade, planned_at, stop_count, known_count] = values[..] else {
+        return Err(Error::Malformed { line });
+    };
+    let trip_upgrade = match trip_upgrade {
+        "-" => None,
+        trip_upgrade => Some(upgrade::try_parse(trip_upgrade).ok_or(Error::Malformed { line })?),
+    };
+    let (planned_at, stop_count, known_count): (usize, usize, usize) = (
+        planned_at.parse()?,
+        stop_count.parse()?,
+        known_count.parse()?,
+    );
+
+    let mut positions = (0..stop_count + known_count)
+        .map(|_| {
+            let (index, line) = lines.next().ok_or(Error::Truncated)?;
+            let [x, y] = numbers((index + 1, line.split(' ').collect()))?;
+            let position = game::Position { x, y };
+
+            if !position.is_within_bounds(dimensions) {
+                return Err(Error::Malformed { line: index + 1 });
+            }
+
+            Ok(position)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let known = positions.split_off(stop_count);
+
+    let trip = trip::Trip {
+        upgrade: trip_upgrade,
+        stops: positions,
+        known: known.into_iter().collect(),
+        planned_at,
+    };
+
+    Ok(crate::Bot {
+        map,
+        chunks,
+
+        player,
+        opponents,
+        // NOTE: Only ever about the turn just played
+        events: Box::default(),
+
+        turn,
+        profile,
+        upgrade_queue_index,
+        trip,
+
+        cage,
+    })
+}
+
+fn field<'input, Lines>(
+    lines: &mut Lines,
+    name: &'static str,
+) -> ::core::result::Result<(usize, Vec<&'input str>), Error>
+where
+    Lines: Iterator<Item = (usize, &'input str)>,
+{
+    let (index, line) = lines.next().ok_or(Error::Truncated)?;
+
+    line.strip_prefix(name)
+        .and_then(|value| value.strip_prefix(' '))
+        .map(|value| (index + 1, value.split(' ').collect()))
+        .ok_or(Error::Malformed { line: index + 1 })
+}
+
+fn numbers<const N: usize, T>(
+    (line, values): (usize, Vec<&str>),
+) -> ::core::result::Result<[T; N], Error>
+where
+    T: ::core::str::FromStr<Err = ::core::num::ParseIntError>,
+{
+    let values: [&str; N] = values.try_into().map_err(|_| Error::Malformed { line })?;
+
+    Ok(values.try_map(str::parse)?)
+}
+
+mod confidence
+{
+    use crate::opponents;
+
+    pub(super) fn show(confidence: opponents::Confidence) -> u8
+    {
+        use opponents::Confidence as C;
+
+        match confidence {
+            C::Assumed => 0,
+            C::Possible => 1,
+            C::Certain => 2,
+        }
+    }
+
+    pub(super) fn try_parse(input: u8) -> Option<opponents::Confidence>
+    {
+        use opponents::Confidence as C;
+
+        match input {
+            0 => Some(C::Assumed),
+            1 => Some(C::Possible),
+            2 => Some(C::Certain),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error
+{
+    Truncated,
+    Malformed
+    {
+        line: usize,
+    },
+    UnsupportedVersion
+    {
+        version: usize,
+    },
+    ParseInt
+    {
+        parse_int_err: ::core::num::ParseIntError,
+    },
+    Tile
+    {
+        tile_err: tile::Error,
+    },
+}
+
+impl ::core::fmt::Display for Error
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result
+    {
+        match self {
+            Error::Truncated => write!(f, "truncated snapshot"),
+            Error::Malformed { line } => write!(f, "malformed snapshot on line {line}"),
+            Error::UnsupportedVersion { version } => {
+                write!(f, "unsupported snapshot format version {version}")
+            }
+            Error::ParseInt { parse_int_err } => write!(f, "{parse_int_err}"),
+            Error::Tile { tile_err } => write!(f, "{tile_err}"),
+        }
+    }
+}
+
+impl ::core::error::Error for Error
+{
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)>
+    {
+        match self {
+            Error::ParseInt { parse_int_err } => Some(parse_int_err),
+            Error::Tile { tile_err } => Some(tile_err),
+            Error::Truncated | Error::Malformed { .. } | Error::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+impl From<::core::num::ParseIntError> for Error
+{
+    fn from(parse_int_err: ::core::num::ParseIntError) -> Self
+    {
+        Error::ParseInt { parse_int_err }
+    }
+}
+
+impl From<tile::Error> for Error
+{
+    fn from(tile_err: tile::Error) -> Self
+    {
+        Error::Tile { tile_err }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,140 @@
+use super::*;
+use crate::sim;
+
+// NOTE: Every snapshot taken along a simulated game reads back into a
+// bot that shows the same and goes on to play the same next turn
+#[test]
+fn parse_reads_back_what_show_wrote()
+{
+    let mut simulator = sim::Simulator::try_init(sim::Config {
+        width: 20,
+        height: 20,
+        players: 2,
+        seed: 1,
+        rounds: 80,
+    })
+    .unwrap();
+    let mut bots = collections::BTreeMap::<u8, crate::Bot>::new();
+
+    while !simulator.is_over() {
+        for id in simulator.alive() {
+            let view = simulator.view(id).unwrap();
+
+            let output = match bots.get_mut(&id) {
+                Some(bot) => {
+                    let shown = show(bot);
+                    let mut restored = try_parse(&shown).unwrap();
+                    assert_eq!(show(&restored), shown, "round {}", simulator.round());
+
+                    let output = bot.turn(&view).unwrap();
+                    assert_eq!(
+                        restored.turn(&view).unwrap(),
+                        output,
+                        "round {}",
+                        simulator.round()
+                    );
+
+                    output
+                }
+                None => {
+                    let (bot, output) =
+                        crate::uninit::try_init_with_profile(&view, crate::Profile::Explorer)
+                            .unwrap();
+                    let _previous = bots.insert(id, bot);
+
+                    output
+                }
+            };
+
+            simulator.try_submit(id, output).unwrap();
+        }
+
+        simulator.step();
+    }
+}
+
+#[test]
+fn parse_rejects_other_format_versions()
+{
+    let version = FORMAT_VERSION + 1;
+
+    assert_eq!(
+        try_parse(format!("snapshot {version}\nturn 0\n")),
+        Err(Error::UnsupportedVersion { version })
+    );
+}
+
+fn snapshot() -> String
+{
+    let simulator = sim::Simulator::try_init(sim::Config::default()).unwrap();
+    let (bot, _) = crate::uninit::try_init(simulator.view(0).unwrap()).unwrap();
+
+    show(&bot)
+}
+
+fn replace_line(shown: &str, name: &str, value: &str) -> String
+{
+    shown
+        .lines()
+        .map(|line| match line.strip_prefix(name) {
+            Some(_) => format!("{name} {value}"),
+            None => line.into(),
+        })
+        .intersperse("\n".into())
+        .collect()
+}
+
+fn line_of(shown: &str, name: &str) -> usize
+{
+    shown
+        .lines()
+        .position(|line| line.starts_with(name))
+        .unwrap()
+        + 1
+}
+
+#[test]
+fn parse_rejects_positions_off_the_map()
+{
+    let shown = snapshot();
+    let line = line_of(&shown, "player ");
+
+    for player in ["500 500 2 2", "2 2 -1 2", "30 2 2 2"] {
+        assert_eq!(
+            try_parse(replace_line(&shown, "player", player)),
+            Err(Error::Malformed { line }),
+            "{player}"
+        );
+    }
+
+    let line = line_of(&shown, "cage ");
+    assert_eq!(
+        try_parse(replace_line(&shown, "cage", "0 U 40 40")),
+        Err(Error::Malformed { line })
+    );
+}
+
+#[test]
+fn parse_rejects_empty_maps()
+{
+    let shown = snapshot();
+    let line = line_of(&shown, "map ");
+
+    for map in ["0 30", "30 0", "0 0"] {
+        assert_eq!(
+            try_parse(replace_line(&shown, "map", map)),
+            Err(Error::Malformed { line }),
+            "{map}"
+        );
+    }
+}
+
+#[test]
+fn parse_rejects_negative_opponent_fields()
+{
+    let shown = snapshot();
+    assert!(shown.contains("\nopponents 0\n"));
+    let shown = shown.replace("\nopponents 0\n", "\nopponents 1\n-1 3 3 1 0 1 0 1 0\n");
+
+    assert!(matches!(try_parse(shown), Err(Error::ParseInt { .. })));
+}
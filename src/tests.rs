@@ -0,0 +1,24 @@
+use super::*;
+
+fn input(stats: &str) -> String
+{
+    format!("5 5\n.....\n.....\n..0..\n.....\n.....\n2 2\n{stats}\n0 0 0")
+}
+
+#[test]
+fn resume_picks_up_the_upgrade_queue_where_the_stats_leave_it()
+{
+    for (stats, taken) in [
+        ("10 1 1 1 1 0 0", 0),
+        ("10 1 1 1 1 0 1", 1),
+        // NOTE: Sight, wheel and rifle at 2, the second sight is next
+        ("10 1 2 2 2 0 1", 4),
+        // NOTE: Ahead of the queue on wheels, behind on battery
+        ("10 1 1 3 1 0 0", 0),
+        ("10 3 3 3 3 1 1", constants::upgrade::QUEUE.len()),
+    ] {
+        let (bot, _) = uninit::try_resume(input(stats), Profile::default(), 40, None).unwrap();
+
+        assert_eq!(bot.upgrade_queue_index, taken, "{stats}");
+    }
+}
@@ -0,0 +1,212 @@
+use ::std::collections;
+
+use crate::{constants::trip, game, map};
+
+// NOTE: Ores to collect in order, making up what the
+// next upgrade still needs before heading back to base
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct Trip
+{
+    pub(super) upgrade: Option<game::Upgrade>,
+    pub(super) stops: Vec<game::Position>,
+    // NOTE: Every ore known of when planning, only
+    // newly revealed ones call for a new plan
+    pub(super) known: collections::HashSet<game::Position>,
+    pub(super) planned_at: usize,
+}
+
+const ORES: [game::NonPlayerTile; 2] = [game::NonPlayerTile::Osmium, game::NonPlayerTile::Iron];
+
+impl Trip
+{
+    pub(super) fn update_with(
+        &mut self,
+        map: &map::Map,
+        player: &game::Player,
+        upgrade: Option<game::Upgrade>,
+        traveller: map::Traveller,
+        seen_since: usize,
+    )
+    {
+        // NOTE: Stops mined along the way, whether
+        // by us or by anyone else, are done with
+        let planned = !self.stops.is_empty();
+        self.stops
+            .retain(|&stop| map.tile_at_is(stop, |np_tile: &_| ORES.contains(np_tile)));
+        let finished = planned && self.stops.is_empty();
+
+        let known = ORES
+            .iter()
+            .flat_map(|&ore| map.seen_tiles(ore, Some(seen_since)))
+            .collect::<collections::HashSet<_>>();
+        let revealed = !known.is_subset(&self.known);
+        // NOTE: Newly revealed ores are weighed in at once when
+        // there's no plan to follow, otherwise every few turns
+        let due =
+            self.stops.is_empty() || traveller.turn >= self.planned_at + trip::REPLAN_INTERVAL;
+
+        if self.upgrade != upgrade || finished || revealed && due {
+            *self = plan(map, player, upgrade, traveller, known);
+        }
+    }
+
+    pub(super) fn next_stop(&self) -> Option<game::Position>
+    {
+        self.stops.first().copied()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Candidate
+{
+    position: game::Position,
+    ore: game::NonPlayerTile,
+
+    from_player: usize,
+    to_base: usize,
+}
+
+// NOTE: Out of the nearest few ores of each kind, the set falling the
+// least short of the upgrade, then taking the fewest turns from the
+// player through all of them and back to base, visited in that order
+fn plan(
+    map: &map::Map,
+    player: &game::Player,
+    upgrade: Option<game::Upgrade>,
+    traveller: map::Traveller,
+    known: collections::HashSet<game::Position>,
+) -> Trip
+{
+    let needs = upgrade
+        .and_then(|upgrade| player.shortfall(upgrade))
+        .unwrap_or_default();
+
+    let width = map.dimensions.width;
+    let from_player = map.turns_from(player.position, traveller);
+    let candidates = [
+        (game::NonPlayerTile::Osmium, needs.osmium),
+        (game::NonPlayerTile::Iron, needs.iron),
+    ]
+    .into_iter()
+    .flat_map(|(ore, need)| {
+        let count = match need as usize {
+            0 => 0,
+            need => (need + trip::SPARE_STOPS).min(trip::MAX_STOPS / ORES.len()),
+        };
+
+        let mut ores = map
+            .seen_tiles(ore, None)
+            .filter(|position| known.contains(position))
+            .collect::<Vec<_>>();
+        ores.sort_by_key(|&position| map.distance_to(position));
+        ores.truncate(count);
+
+        ores.into_iter().map(move |position| (position, ore))
+    })
+    .filter_map(|(position, ore)| {
+        let turns_from = map.turns_from(position, traveller);
+
+        Some((
+            Candidate {
+                position,
+                ore,
+
+                from_player: from_player[position.to_linear(width)]?,
+                to_base: turns_from[player.base.to_linear(width)]?,
+            },
+            turns_from,
+        ))
+    })
+    .collect::<Vec<_>>();
+    let (candidates, turns_from): (Vec<_>, Vec<_>) = candidates.into_iter().unzip();
+
+    // NOTE: Legs are read off one search from each candidate,
+    // a candidate to itself is never one
+    let legs = turns_from
+        .iter()
+        .map(|turns_from| {
+            candidates
+                .iter()
+                .map(|to| turns_from[to.position.to_linear(width)])
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // NOTE: Held-Karp, the fewest turns through every set of
+    // candidates ending at each one, along with the one before it
+    let count = candidates.len();
+    let mut through = vec![vec![None::<(usize, usize)>; count]; 1 << count];
+    for (index, Candidate { from_player, .. }) in candidates.iter().enumerate() {
+        through[1 << index][index] = Some((*from_player, index));
+    }
+    for set in 1..1 << count {
+        for last in (0..count).filter(|last| set & 1 << last != 0) {
+            let Some((turns, _)) = through[set][last] else {
+                continue;
+            };
+
+            for next in (0..count).filter(|next| set & 1 << next == 0) {
+                let Some(leg) = legs[last][next] else {
+                    continue;
+                };
+
+                let next_set = set | 1 << next;
+                if through[next_set][next].is_none_or(|(best, _)| turns + leg < best) {
+                    through[next_set][next] = Some((turns + leg, last));
+                }
+            }
+        }
+    }
+
+    let shortfall = |set: usize| {
+        let collected = |ore| {
+            (0..count)
+                .filter(|index| set & 1 << index != 0 && candidates[*index].ore == ore)
+                .count()
+        };
+
+        (needs.osmium as usize).saturating_sub(collected(game::NonPlayerTile::Osmium))
+            + (needs.iron as usize).saturating_sub(collected(game::NonPlayerTile::Iron))
+    };
+
+    let candidates = &candidates;
+    let best = (1..1 << count)
+        .flat_map(|set| {
+            let through = &through[set];
+
+            (0..count).filter_map(move |last| {
+                let (turns, _) = through[last]?;
+
+                Some((set, last, turns + candidates[last].to_base))
+            })
+        })
+        .min_by_key(|&(set, _, turns)| (shortfall(set), turns))
+        .filter(|&(set, _, _)| shortfall(set) < shortfall(0));
+
+    let mut stops = Vec::new();
+    if let Some((mut set, mut last, _)) = best {
+        loop {
+            stops.push(candidates[last].position);
+
+            // SAFETY: Only sets reached through some order are ever picked
+            let (_, previous) = through[set][last].unwrap();
+            if previous == last {
+                break;
+            }
+
+            set &= !(1 << last);
+            last = previous;
+        }
+        stops.reverse();
+    }
+
+    Trip {
+        upgrade,
+        stops,
+        known,
+        planned_at: traveller.turn,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,145 @@
+use super::*;
+
+const TURN: usize = 10;
+
+// NOTE: `0` stands for the player and `E` for its base, every tile
+// has been seen at `TURN`. One more iron makes for the sight upgrade
+fn scene(rows: &[&str]) -> (map::Map, game::Player)
+{
+    let dimensions = game::Dimensions {
+        width: rows[0].len(),
+        height: rows.len(),
+    };
+
+    let (mut position, mut base) = Default::default();
+    let entries = rows
+        .iter()
+        .flat_map(|row| row.bytes())
+        .enumerate()
+        .map(|(index, byte)| {
+            let at = game::Position::from_linear(index, dimensions.width);
+            let tile = match byte {
+                b'0' => {
+                    position = at;
+
+                    game::Tile::Air
+                }
+                byte => crate::io::input::map::tile::try_parse(byte).unwrap(),
+            };
+            if tile == game::Tile::Base {
+                base = at;
+            }
+
+            let mut entry = map::Entry::init(tile);
+            entry.last_seen = Some(TURN);
+
+            entry
+        })
+        .collect();
+
+    let player = game::Player {
+        position,
+        stats: game::player::Stats {
+            hit_points: 10,
+
+            drill_level: 1,
+            rifle_level: 1,
+            wheel_level: 1,
+            sight_level: 1,
+
+            has_antenna: false,
+            has_battery: false,
+        },
+        inventory: game::player::Inventory {
+            stone: 0,
+            iron: 1,
+            osmium: 0,
+        },
+
+        base,
+    };
+
+    let mut map = map::Map::new(dimensions, entries, map::CostModel::Turns);
+    map.flood_fill(position, traveller(&player, TURN));
+
+    (map, player)
+}
+
+fn traveller(player: &game::Player, turn: usize) -> map::Traveller
+{
+    map::Traveller {
+        wheel_level: player.stats.wheel_level,
+        drill_level: player.stats.drill_level,
+        turn,
+    }
+}
+
+fn planned(map: &map::Map, player: &game::Player, upgrade: Option<game::Upgrade>) -> Trip
+{
+    let mut trip = Trip::default();
+    trip.update_with(map, player, upgrade, traveller(player, TURN), TURN);
+
+    trip
+}
+
+// NOTE: Both ores are as near as each other, but only
+// going for the far side first passes by the base after
+#[test]
+fn plan_takes_the_fewest_turns_back_to_base()
+{
+    let (map, player) = scene(&["E.C.0.C.."]);
+
+    assert_eq!(
+        planned(&map, &player, Some(game::Upgrade::Sight)).stops,
+        [game::Position { x: 6, y: 0 }, game::Position { x: 2, y: 0 }]
+    );
+}
+
+#[test]
+fn plan_stops_at_what_the_upgrade_needs()
+{
+    let (map, player) = scene(&["E.C.0.C.C", "........D"]);
+
+    assert_eq!(
+        planned(&map, &player, Some(game::Upgrade::Sight)).stops,
+        [game::Position { x: 6, y: 0 }, game::Position { x: 2, y: 0 }]
+    );
+    assert_eq!(planned(&map, &player, None).stops, []);
+}
+
+#[test]
+fn update_with_drops_mined_stops_and_weighs_in_revealed_ores()
+{
+    let (map, player) = scene(&["E.C.0.C.."]);
+    let mut trip = planned(&map, &player, Some(game::Upgrade::Sight));
+
+    // NOTE: Taken by someone else
+    let (map, player) = scene(&["E.C.0...."]);
+    trip.update_with(
+        &map,
+        &player,
+        Some(game::Upgrade::Sight),
+        traveller(&player, TURN + 1),
+        TURN,
+    );
+    assert_eq!(trip.stops, [game::Position { x: 2, y: 0 }]);
+
+    // NOTE: Only once the plan's been followed for a while
+    let (map, player) = scene(&["E.C.0C..."]);
+    for (turn, stops) in [
+        (TURN + 2, vec![game::Position { x: 2, y: 0 }]),
+        (
+            TURN + trip::REPLAN_INTERVAL,
+            vec![game::Position { x: 5, y: 0 }, game::Position { x: 2, y: 0 }],
+        ),
+    ] {
+        trip.update_with(
+            &map,
+            &player,
+            Some(game::Upgrade::Sight),
+            traveller(&player, turn),
+            TURN,
+        );
+        assert_eq!(trip.stops, stops);
+    }
+}